@@ -91,6 +91,7 @@ impl ActivityManager {
             protocol,
             username,
             password,
+            post_connect_cmd: None,
         });
     }
 
@@ -164,6 +165,7 @@ impl ActivityManager {
                         _ => Some(activity.password.clone()),
                     },
                     protocol: activity.protocol,
+                    post_connect_cmd: activity.post_connect_cmd.clone(),
                 });
                 break;
             }
@@ -0,0 +1,172 @@
+//! ## Builder
+//!
+//! `builder` is the module which provides a builder for bookmarks
+
+/*
+*
+*   Copyright (C) 2020-2021Christian Visintin - christian.visintin1997@gmail.com
+*
+* 	This file is part of "TermSCP"
+*
+*   TermSCP is free software: you can redistribute it and/or modify
+*   it under the terms of the GNU General Public License as published by
+*   the Free Software Foundation, either version 3 of the License, or
+*   (at your option) any later version.
+*
+*   TermSCP is distributed in the hope that it will be useful,
+*   but WITHOUT ANY WARRANTY; without even the implied warranty of
+*   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+*   GNU General Public License for more details.
+*
+*   You should have received a copy of the GNU General Public License
+*   along with TermSCP.  If not, see <http://www.gnu.org/licenses/>.
+*
+*/
+
+// Locals
+use crate::filetransfer::FileTransferProtocol;
+
+/// ## NewBookmark
+///
+/// NewBookmark describes a bookmark to add to `BookmarksClient`, with a plain password.
+/// Optional fields which are not set are kept from the bookmark being replaced, if any
+#[derive(Clone, Debug, PartialEq)]
+pub struct NewBookmark {
+    pub name: String,
+    pub address: String,
+    pub port: u16,
+    pub protocol: FileTransferProtocol,
+    pub username: String,
+    pub password: Option<String>,
+    pub post_connect_cmd: Option<String>,
+}
+
+/// ## BookmarkBuilder
+///
+/// Struct used to create a `NewBookmark`
+pub struct BookmarkBuilder {
+    bookmark: Option<NewBookmark>,
+}
+
+impl Default for BookmarkBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BookmarkBuilder {
+    /// ### new
+    ///
+    /// Build a new `BookmarkBuilder`; protocol is SFTP and port is 22, unless set
+    pub fn new() -> Self {
+        BookmarkBuilder {
+            bookmark: Some(NewBookmark {
+                name: String::new(),
+                address: String::new(),
+                port: 22,
+                protocol: FileTransferProtocol::Sftp,
+                username: String::new(),
+                password: None,
+                post_connect_cmd: None,
+            }),
+        }
+    }
+
+    /// ### build
+    ///
+    /// Take NewBookmark out of builder
+    pub fn build(&mut self) -> NewBookmark {
+        self.bookmark.take().unwrap()
+    }
+
+    /// ### with_name
+    ///
+    /// Set bookmark name
+    pub fn with_name(&mut self, name: &str) -> &mut BookmarkBuilder {
+        if let Some(b) = self.bookmark.as_mut() {
+            b.name = name.to_string();
+        }
+        self
+    }
+
+    /// ### with_address
+    ///
+    /// Set remote address
+    pub fn with_address(&mut self, address: &str) -> &mut BookmarkBuilder {
+        if let Some(b) = self.bookmark.as_mut() {
+            b.address = address.to_string();
+        }
+        self
+    }
+
+    /// ### with_port
+    ///
+    /// Set remote port
+    pub fn with_port(&mut self, port: u16) -> &mut BookmarkBuilder {
+        if let Some(b) = self.bookmark.as_mut() {
+            b.port = port;
+        }
+        self
+    }
+
+    /// ### with_protocol
+    ///
+    /// Set file transfer protocol
+    pub fn with_protocol(&mut self, protocol: FileTransferProtocol) -> &mut BookmarkBuilder {
+        if let Some(b) = self.bookmark.as_mut() {
+            b.protocol = protocol;
+        }
+        self
+    }
+
+    /// ### with_username
+    ///
+    /// Set username
+    pub fn with_username(&mut self, username: &str) -> &mut BookmarkBuilder {
+        if let Some(b) = self.bookmark.as_mut() {
+            b.username = username.to_string();
+        }
+        self
+    }
+
+    /// ### with_password
+    ///
+    /// Set password to save with bookmark
+    pub fn with_password(&mut self, password: Option<&str>) -> &mut BookmarkBuilder {
+        if let Some(b) = self.bookmark.as_mut() {
+            b.password = password.map(String::from);
+        }
+        self
+    }
+
+    /// ### with_post_connect_cmd
+    ///
+    /// Set local command to run after connecting
+    pub fn with_post_connect_cmd(&mut self, cmd: &str) -> &mut BookmarkBuilder {
+        if let Some(b) = self.bookmark.as_mut() {
+            b.post_connect_cmd = Some(cmd.to_string());
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_bookmarks_builder_new_default() {
+        let bookmark: NewBookmark = BookmarkBuilder::new()
+            .with_name("raspberry")
+            .with_address("192.168.1.31")
+            .build();
+        assert_eq!(bookmark.name.as_str(), "raspberry");
+        assert_eq!(bookmark.address.as_str(), "192.168.1.31");
+        assert_eq!(bookmark.port, 22);
+        assert_eq!(bookmark.protocol, FileTransferProtocol::Sftp);
+        assert!(bookmark.username.is_empty());
+        assert!(bookmark.password.is_none());
+        assert!(bookmark.post_connect_cmd.is_none());
+    }
+}
@@ -23,22 +23,32 @@
 *
 */
 
+pub mod builder;
 pub mod serializer;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 
-#[derive(Deserialize, Serialize, std::fmt::Debug)]
+/// Version of the bookmarks file schema written by this version of termscp.
+/// Must be increased each time fields which older versions can't represent are added
+pub const BOOKMARKS_SCHEMA_VERSION: u32 = 1;
+/// Display order of bookmarks which haven't been arranged yet; they're shown after the arranged ones
+pub const DEFAULT_BOOKMARK_ORDER: u32 = u32::MAX;
+
+#[derive(Clone, Deserialize, Serialize, std::fmt::Debug)]
 /// ## UserHosts
 ///
 /// UserHosts contains all the hosts saved by the user in the data storage
 /// It contains both `Bookmark`
 pub struct UserHosts {
+    #[serde(default)]
+    pub schema_version: u32, // Files written before versioning was introduced are `0`
     pub bookmarks: HashMap<String, Bookmark>,
     pub recents: HashMap<String, Bookmark>,
 }
 
-#[derive(Deserialize, Serialize, std::fmt::Debug, PartialEq)]
+#[derive(Clone, Deserialize, Serialize, std::fmt::Debug, PartialEq)]
 /// ## Bookmark
 ///
 /// Bookmark describes a single bookmark entry in the user hosts storage
@@ -48,6 +58,67 @@ pub struct Bookmark {
     pub protocol: String,
     pub username: String,
     pub password: Option<String>, // Password is optional; base64, aes-128 encrypted password
+    #[serde(default)]
+    pub color: Option<String>, // Color to display bookmark with; named color or hex
+    #[serde(default)]
+    pub icon: Option<String>, // Icon to display beside bookmark name
+    #[serde(
+        default = "default_bookmark_order",
+        skip_serializing_if = "is_default_bookmark_order"
+    )]
+    pub order: u32, // Display order; lower comes first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_connect_cmd: Option<String>, // Local command to run after connecting; stored as plain text
+}
+
+/// ## RecentsPolicy
+///
+/// RecentsPolicy describes how recents are pruned when a new recent is added.
+/// Recents older than `max_age` are removed first, then the oldest ones are removed until `max` is respected.
+/// If `max` is `None`, recents are unlimited
+#[derive(Clone, Copy, std::fmt::Debug, PartialEq)]
+pub struct RecentsPolicy {
+    pub max: Option<usize>,
+    pub max_age: Option<Duration>,
+}
+
+impl From<usize> for RecentsPolicy {
+    /// `usize::MAX` stands for unlimited recents
+    fn from(max: usize) -> Self {
+        RecentsPolicy {
+            max: match max {
+                usize::MAX => None,
+                max => Some(max),
+            },
+            max_age: None,
+        }
+    }
+}
+
+/// ### default_bookmark_order
+///
+/// Bookmarks which haven't been arranged yet get the default order
+fn default_bookmark_order() -> u32 {
+    DEFAULT_BOOKMARK_ORDER
+}
+
+/// ### is_default_bookmark_order
+///
+/// Returns whether order is the default one, so it doesn't need to be written
+fn is_default_bookmark_order(order: &u32) -> bool {
+    *order == DEFAULT_BOOKMARK_ORDER
+}
+
+/// ### sanitize_bookmark_name
+///
+/// Make a bookmark name out of a raw string provided by the user:
+/// path separators are replaced by whitespaces, then whitespaces are trimmed and collapsed into a single space.
+/// e.g. `"  prod / web  "` becomes `"prod web"`
+pub fn sanitize_bookmark_name(raw: &str) -> String {
+    raw.replace(['/', '\\'], " ")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
 }
 
 // Errors
@@ -67,6 +138,7 @@ pub struct SerializerError {
 #[derive(std::fmt::Debug, PartialEq)]
 pub enum SerializerErrorKind {
     IoError,
+    IntegrityError,
     SerializationError,
     SyntaxError,
 }
@@ -74,6 +146,7 @@ pub enum SerializerErrorKind {
 impl Default for UserHosts {
     fn default() -> Self {
         UserHosts {
+            schema_version: BOOKMARKS_SCHEMA_VERSION,
             bookmarks: HashMap::new(),
             recents: HashMap::new(),
         }
@@ -102,6 +175,7 @@ impl std::fmt::Display for SerializerError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let err: String = match &self.kind {
             SerializerErrorKind::IoError => String::from("IO error"),
+            SerializerErrorKind::IntegrityError => String::from("Integrity error"),
             SerializerErrorKind::SerializationError => String::from("Serialization error"),
             SerializerErrorKind::SyntaxError => String::from("Syntax error"),
         };
@@ -127,6 +201,10 @@ mod tests {
             protocol: String::from("SFTP"),
             username: String::from("root"),
             password: Some(String::from("password")),
+            color: None,
+            icon: None,
+            order: DEFAULT_BOOKMARK_ORDER,
+            post_connect_cmd: None,
         };
         let recent: Bookmark = Bookmark {
             address: String::from("192.168.1.2"),
@@ -134,16 +212,22 @@ mod tests {
             protocol: String::from("SCP"),
             username: String::from("admin"),
             password: Some(String::from("password")),
+            color: None,
+            icon: None,
+            order: DEFAULT_BOOKMARK_ORDER,
+            post_connect_cmd: None,
         };
         let mut bookmarks: HashMap<String, Bookmark> = HashMap::with_capacity(1);
         bookmarks.insert(String::from("test"), bookmark);
         let mut recents: HashMap<String, Bookmark> = HashMap::with_capacity(1);
         recents.insert(String::from("ISO20201218T181432"), recent);
         let hosts: UserHosts = UserHosts {
+            schema_version: BOOKMARKS_SCHEMA_VERSION,
             bookmarks: bookmarks,
             recents: recents,
         };
         // Verify
+        assert_eq!(hosts.schema_version, BOOKMARKS_SCHEMA_VERSION);
         let bookmark: &Bookmark = hosts.bookmarks.get(&String::from("test")).unwrap();
         assert_eq!(bookmark.address, String::from("192.168.1.1"));
         assert_eq!(bookmark.port, 22);
@@ -167,6 +251,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bookmarks_recents_policy_from_size() {
+        assert_eq!(
+            RecentsPolicy::from(16),
+            RecentsPolicy {
+                max: Some(16),
+                max_age: None
+            }
+        );
+        assert_eq!(
+            RecentsPolicy::from(usize::MAX),
+            RecentsPolicy {
+                max: None,
+                max_age: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_bookmarks_sanitize_bookmark_name() {
+        assert_eq!(sanitize_bookmark_name("  prod / web  "), "prod web");
+        assert_eq!(sanitize_bookmark_name("raspberry"), "raspberry");
+        assert_eq!(sanitize_bookmark_name("my\tbox\\home\n"), "my box home");
+        assert_eq!(sanitize_bookmark_name(" / "), "");
+    }
+
     #[test]
     fn test_bookmarks_bookmark_errors() {
         let error: SerializerError = SerializerError::new(SerializerErrorKind::SyntaxError);
@@ -23,44 +23,98 @@
 *
 */
 
-use super::{SerializerError, SerializerErrorKind, UserHosts};
+use super::{Bookmark, SerializerError, SerializerErrorKind, UserHosts};
 
-use std::io::{Read, Write};
+use serde::Serialize;
+use std::collections::BTreeMap;
+#[cfg(test)]
+use std::io::Read;
+use std::io::{BufWriter, Write};
 
 pub struct BookmarkSerializer {}
 
+/// ## SortedUserHosts
+///
+/// View of `UserHosts` with bookmarks and recents sorted by key
+#[derive(Serialize)]
+struct SortedUserHosts<'a> {
+    schema_version: u32,
+    bookmarks: BTreeMap<&'a String, &'a Bookmark>,
+    recents: BTreeMap<&'a String, &'a Bookmark>,
+}
+
+/// ## HostsChunk
+///
+/// Part of `UserHosts` serialized on its own, while streaming hosts
+#[derive(Serialize)]
+struct HostsChunk<'a> {
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    bookmarks: BTreeMap<&'a String, &'a Bookmark>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    recents: BTreeMap<&'a String, &'a Bookmark>,
+}
+
 impl BookmarkSerializer {
     /// ### serialize
     ///
-    /// Serialize `UserHosts` into TOML and write content to writable
+    /// Serialize `UserHosts` into TOML and write content to writable.
+    /// Hosts are serialized and written one at a time, sorted by key, so the whole document is never kept in memory
     pub fn serialize(
         &self,
-        mut writable: Box<dyn Write>,
+        writable: Box<dyn Write>,
         hosts: &UserHosts,
     ) -> Result<(), SerializerError> {
-        // Serialize content
-        let data: String = match toml::ser::to_string(hosts) {
-            Ok(dt) => dt,
-            Err(err) => {
-                return Err(SerializerError::new_ex(
-                    SerializerErrorKind::SerializationError,
-                    err.to_string(),
-                ))
-            }
+        let mut writer: BufWriter<Box<dyn Write>> = BufWriter::new(writable);
+        // Write schema version and empty tables; hosts are then appended as sub-tables
+        let header: SortedUserHosts = SortedUserHosts {
+            schema_version: hosts.schema_version,
+            bookmarks: BTreeMap::new(),
+            recents: BTreeMap::new(),
         };
-        // Write file
-        match writable.write_all(data.as_bytes()) {
-            Ok(_) => Ok(()),
-            Err(err) => Err(SerializerError::new_ex(
-                SerializerErrorKind::IoError,
-                err.to_string(),
-            )),
+        Self::write_toml(&mut writer, &header)?;
+        let mut bookmarks: Vec<(&String, &Bookmark)> = hosts.bookmarks.iter().collect();
+        bookmarks.sort_by(|a, b| a.0.cmp(b.0));
+        let mut recents: Vec<(&String, &Bookmark)> = hosts.recents.iter().collect();
+        recents.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, bookmark) in bookmarks.into_iter() {
+            let mut chunk: HostsChunk = HostsChunk {
+                bookmarks: BTreeMap::new(),
+                recents: BTreeMap::new(),
+            };
+            chunk.bookmarks.insert(name, bookmark);
+            Self::write_toml(&mut writer, &chunk)?;
+        }
+        for (key, recent) in recents.into_iter() {
+            let mut chunk: HostsChunk = HostsChunk {
+                bookmarks: BTreeMap::new(),
+                recents: BTreeMap::new(),
+            };
+            chunk.recents.insert(key, recent);
+            Self::write_toml(&mut writer, &chunk)?;
         }
+        writer
+            .flush()
+            .map_err(|err| SerializerError::new_ex(SerializerErrorKind::IoError, err.to_string()))
+    }
+
+    /// ### write_toml
+    ///
+    /// Serialize `data` into TOML and write it to `writer`
+    fn write_toml<T: Serialize>(
+        writer: &mut BufWriter<Box<dyn Write>>,
+        data: &T,
+    ) -> Result<(), SerializerError> {
+        let data: String = toml::ser::to_string(data).map_err(|err| {
+            SerializerError::new_ex(SerializerErrorKind::SerializationError, err.to_string())
+        })?;
+        writeln!(writer, "{}", data)
+            .map_err(|err| SerializerError::new_ex(SerializerErrorKind::IoError, err.to_string()))
     }
 
     /// ### deserialize
     ///
     /// Read data from readable and deserialize its content as TOML
+    #[cfg(test)]
     pub fn deserialize(&self, mut readable: Box<dyn Read>) -> Result<UserHosts, SerializerError> {
         // Read file content
         let mut data: String = String::new();
@@ -71,7 +125,46 @@ impl BookmarkSerializer {
             ));
         }
         // Deserialize
-        match toml::de::from_str(data.as_str()) {
+        self.deserialize_from_str(data.as_str())
+    }
+
+    /// ### serialize_to_string
+    ///
+    /// Serialize `UserHosts` into a TOML string
+    pub fn serialize_to_string(&self, hosts: &UserHosts) -> Result<String, SerializerError> {
+        match toml::ser::to_string(hosts) {
+            Ok(dt) => Ok(dt),
+            Err(err) => Err(SerializerError::new_ex(
+                SerializerErrorKind::SerializationError,
+                err.to_string(),
+            )),
+        }
+    }
+
+    /// ### serialize_sorted_to_string
+    ///
+    /// Serialize `UserHosts` into a TOML string, with bookmarks sorted by name and recents sorted by key,
+    /// so that the output doesn't depend on the order entries have been inserted
+    pub fn serialize_sorted_to_string(&self, hosts: &UserHosts) -> Result<String, SerializerError> {
+        let sorted: SortedUserHosts = SortedUserHosts {
+            schema_version: hosts.schema_version,
+            bookmarks: hosts.bookmarks.iter().collect(),
+            recents: hosts.recents.iter().collect(),
+        };
+        match toml::ser::to_string(&sorted) {
+            Ok(dt) => Ok(dt),
+            Err(err) => Err(SerializerError::new_ex(
+                SerializerErrorKind::SerializationError,
+                err.to_string(),
+            )),
+        }
+    }
+
+    /// ### deserialize_from_str
+    ///
+    /// Deserialize `UserHosts` from a TOML string
+    pub fn deserialize_from_str(&self, data: &str) -> Result<UserHosts, SerializerError> {
+        match toml::de::from_str(data) {
             Ok(hosts) => Ok(hosts),
             Err(err) => Err(SerializerError::new_ex(
                 SerializerErrorKind::SyntaxError,
@@ -86,7 +179,7 @@ impl BookmarkSerializer {
 #[cfg(test)]
 mod tests {
 
-    use super::super::Bookmark;
+    use super::super::{BOOKMARKS_SCHEMA_VERSION, DEFAULT_BOOKMARK_ORDER};
     use super::*;
 
     use std::collections::HashMap;
@@ -102,7 +195,8 @@ mod tests {
         let hosts = deserializer.deserialize(Box::new(toml_file));
         assert!(hosts.is_ok());
         let hosts: UserHosts = hosts.ok().unwrap();
-        // Verify hosts
+        // Verify hosts; file is not versioned
+        assert_eq!(hosts.schema_version, 0);
         // Verify recents
         assert_eq!(hosts.recents.len(), 1);
         let host: &Bookmark = hosts.recents.get("ISO20201215T094000Z").unwrap();
@@ -119,6 +213,7 @@ mod tests {
         assert_eq!(host.protocol, String::from("SFTP"));
         assert_eq!(host.username, String::from("root"));
         assert_eq!(*host.password.as_ref().unwrap(), String::from("mypassword"));
+        assert_eq!(host.order, 1);
         let host: &Bookmark = hosts.bookmarks.get("msi-estrem").unwrap();
         assert_eq!(host.address, String::from("192.168.1.30"));
         assert_eq!(host.port, 22);
@@ -131,6 +226,7 @@ mod tests {
         assert_eq!(host.protocol, String::from("FTPS"));
         assert_eq!(host.username, String::from("aws001"));
         assert_eq!(host.password, None);
+        assert_eq!(host.order, DEFAULT_BOOKMARK_ORDER);
     }
 
     #[test]
@@ -155,6 +251,10 @@ mod tests {
                 protocol: String::from("SFTP"),
                 username: String::from("root"),
                 password: None,
+                color: None,
+                icon: None,
+                order: DEFAULT_BOOKMARK_ORDER,
+                post_connect_cmd: None,
             },
         );
         bookmarks.insert(
@@ -165,6 +265,10 @@ mod tests {
                 protocol: String::from("SFTP"),
                 username: String::from("cvisintin"),
                 password: Some(String::from("password")),
+                color: None,
+                icon: None,
+                order: DEFAULT_BOOKMARK_ORDER,
+                post_connect_cmd: None,
             },
         );
         let mut recents: HashMap<String, Bookmark> = HashMap::with_capacity(1);
@@ -176,21 +280,67 @@ mod tests {
                 protocol: String::from("SCP"),
                 username: String::from("omar"),
                 password: Some(String::from("aaa")),
+                color: None,
+                icon: None,
+                order: DEFAULT_BOOKMARK_ORDER,
+                post_connect_cmd: None,
             },
         );
         let tmpfile: tempfile::NamedTempFile = tempfile::NamedTempFile::new().unwrap();
         // Serialize
         let deserializer: BookmarkSerializer = BookmarkSerializer {};
-        let hosts: UserHosts = UserHosts { bookmarks, recents };
+        let hosts: UserHosts = UserHosts {
+            schema_version: BOOKMARKS_SCHEMA_VERSION,
+            bookmarks,
+            recents,
+        };
         assert!(deserializer.serialize(Box::new(tmpfile), &hosts).is_ok());
     }
 
+    #[test]
+    fn test_bookmarks_serializer_serialize_large() {
+        let mut bookmarks: HashMap<String, Bookmark> = HashMap::with_capacity(5000);
+        let mut recents: HashMap<String, Bookmark> = HashMap::with_capacity(16);
+        for i in 0..5000 {
+            let bookmark: Bookmark = Bookmark {
+                address: format!("10.0.{}.{}", i / 256, i % 256),
+                port: 22,
+                protocol: String::from("SFTP"),
+                username: format!("user{}", i),
+                password: None,
+                color: None,
+                icon: None,
+                order: DEFAULT_BOOKMARK_ORDER,
+                post_connect_cmd: None,
+            };
+            if i < 16 {
+                recents.insert(format!("ISO20201215T0940{:02}", i), bookmark.clone());
+            }
+            bookmarks.insert(format!("host-{}", i), bookmark);
+        }
+        let hosts: UserHosts = UserHosts {
+            schema_version: BOOKMARKS_SCHEMA_VERSION,
+            bookmarks,
+            recents,
+        };
+        let serializer: BookmarkSerializer = BookmarkSerializer {};
+        let tmpfile: tempfile::NamedTempFile = tempfile::NamedTempFile::new().unwrap();
+        let file: std::fs::File = tmpfile.reopen().unwrap();
+        assert!(serializer.serialize(Box::new(file), &hosts).is_ok());
+        // Read back
+        let file: std::fs::File = tmpfile.reopen().unwrap();
+        let read: UserHosts = serializer.deserialize(Box::new(file)).unwrap();
+        assert_eq!(read.schema_version, BOOKMARKS_SCHEMA_VERSION);
+        assert_eq!(read.bookmarks, hosts.bookmarks);
+        assert_eq!(read.recents, hosts.recents);
+    }
+
     fn create_good_toml() -> tempfile::NamedTempFile {
         // Write
         let mut tmpfile: tempfile::NamedTempFile = tempfile::NamedTempFile::new().unwrap();
         let file_content: &str = r#"
         [bookmarks]
-        raspberrypi2 = { address = "192.168.1.31", port = 22, protocol = "SFTP", username = "root", password = "mypassword" }
+        raspberrypi2 = { address = "192.168.1.31", port = 22, protocol = "SFTP", username = "root", password = "mypassword", order = 1 }
         msi-estrem = { address = "192.168.1.30", port = 22, protocol = "SFTP", username = "cvisintin", password = "mysecret" }
         aws-server-prod1 = { address = "51.23.67.12", port = 21, protocol = "FTPS", username = "aws001" }
 
@@ -37,40 +37,97 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-#[derive(Deserialize, Serialize, std::fmt::Debug)]
+#[derive(Clone, Deserialize, Serialize, std::fmt::Debug)]
 /// ## UserConfig
 ///
 /// UserConfig contains all the configurations for the user,
 /// supported by termscp
 pub struct UserConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_tag: Option<String>, // Tag (e.g. a change ticket) reported in session logs, for auditing
     pub user_interface: UserInterfaceConfig,
     pub remote: RemoteConfig,
+    #[serde(default)]
+    pub recents: RecentsConfig,
 }
 
-#[derive(Deserialize, Serialize, std::fmt::Debug)]
+#[derive(Clone, Deserialize, Serialize, std::fmt::Debug)]
 /// ## UserInterfaceConfig
 ///
 /// UserInterfaceConfig provides all the keys to configure the user interface
 pub struct UserInterfaceConfig {
     pub text_editor: PathBuf,
     pub default_protocol: String,
-    pub show_hidden_files: bool,
+    #[serde(default, skip_serializing)]
+    pub show_hidden_files: Option<bool>, // Deprecated: replaced by `show_hidden_local` and `show_hidden_remote`; migrated on load
+    #[serde(default)]
+    pub show_hidden_local: bool, // Show hidden files in local explorer
+    #[serde(default)]
+    pub show_hidden_remote: bool, // Show hidden files in remote explorer
     pub group_dirs: Option<String>,
+    #[serde(default = "default_true")]
+    pub confirm_remote_delete: bool, // Ask for confirmation before deleting remote files
+    #[serde(default)]
+    pub password_prompt_timeout_secs: Option<u64>, // Cancel password prompt after timeout; `None` to wait forever
+    #[serde(default)]
+    pub temp_dir: Option<PathBuf>, // Directory for temporary files (e.g. remote files being edited); `None` to use system's
+    #[serde(default)]
+    pub notify_on_complete: bool, // Send a desktop notification when a transfer completes
+    #[serde(default = "default_min_terminal")]
+    pub min_terminal: (u16, u16), // Minimum terminal size (columns, rows) required by the layout
+    #[serde(default = "default_enabled_protocols")]
+    pub enabled_protocols: Vec<String>, // Protocols to show in the connect form
+    #[serde(default)]
+    pub protocol_editors: HashMap<String, PathBuf>, // Association between protocol and text editor
+    #[serde(default)]
+    pub protocol_icons: HashMap<String, String>, // Association between protocol and icon to display beside connections
 }
 
-#[derive(Deserialize, Serialize, std::fmt::Debug)]
+#[derive(Clone, Deserialize, Serialize, std::fmt::Debug)]
 /// ## RemoteConfig
 ///
 /// Contains configuratio related to remote hosts
 pub struct RemoteConfig {
+    #[serde(default = "default_transfer_buffer_kb")]
+    pub transfer_buffer_kb: u64, // Size of the buffer used to transfer files, in KB
+    #[serde(default = "default_true")]
+    pub save_passwords: bool, // Whether passwords can be saved with bookmarks
+    #[serde(default)]
+    pub always_ask_credentials: bool, // If true, stored passwords are never filled in automatically
     pub ssh_keys: HashMap<String, PathBuf>, // Association between host name and path to private key
 }
 
+#[derive(Clone, Deserialize, Serialize, std::fmt::Debug)]
+/// ## RecentsConfig
+///
+/// Contains configuration related to recent connections
+pub struct RecentsConfig {
+    pub max: Option<usize>, // Max amount of recents; if not set, recents are unlimited
+    pub max_age: Option<u64>, // Max age of recents in seconds
+    #[serde(default = "default_true")]
+    pub store_username: bool, // Whether to store username in recents
+}
+
+impl UserConfig {
+    /// ### migrate
+    ///
+    /// Migrate deprecated keys, read from configurations written by previous versions, to the current ones
+    pub fn migrate(&mut self) {
+        // `show_hidden_files` applies to both explorers
+        if let Some(show) = self.user_interface.show_hidden_files.take() {
+            self.user_interface.show_hidden_local = show;
+            self.user_interface.show_hidden_remote = show;
+        }
+    }
+}
+
 impl Default for UserConfig {
     fn default() -> Self {
         UserConfig {
+            session_tag: None,
             user_interface: UserInterfaceConfig::default(),
             remote: RemoteConfig::default(),
+            recents: RecentsConfig::default(),
         }
     }
 }
@@ -83,8 +140,18 @@ impl Default for UserInterfaceConfig {
                 Err(_) => PathBuf::from("nano"), // Default to nano
             },
             default_protocol: FileTransferProtocol::Sftp.to_string(),
-            show_hidden_files: false,
+            show_hidden_files: None,
+            show_hidden_local: false,
+            show_hidden_remote: false,
             group_dirs: None,
+            confirm_remote_delete: true,
+            password_prompt_timeout_secs: None,
+            temp_dir: None,
+            notify_on_complete: false,
+            min_terminal: default_min_terminal(),
+            enabled_protocols: default_enabled_protocols(),
+            protocol_editors: HashMap::new(),
+            protocol_icons: HashMap::new(),
         }
     }
 }
@@ -92,11 +159,57 @@ impl Default for UserInterfaceConfig {
 impl Default for RemoteConfig {
     fn default() -> Self {
         RemoteConfig {
+            transfer_buffer_kb: default_transfer_buffer_kb(),
+            save_passwords: true,
+            always_ask_credentials: false,
             ssh_keys: HashMap::new(),
         }
     }
 }
 
+impl Default for RecentsConfig {
+    fn default() -> Self {
+        RecentsConfig {
+            max: Some(16),
+            max_age: None,
+            store_username: true,
+        }
+    }
+}
+
+/// ### default_true
+///
+/// Default value for boolean options which are enabled if not specified
+fn default_true() -> bool {
+    true
+}
+
+/// ### default_min_terminal
+///
+/// Default minimum terminal size is 80 columns by 24 rows
+fn default_min_terminal() -> (u16, u16) {
+    (80, 24)
+}
+
+/// ### default_enabled_protocols
+///
+/// By default all protocols are enabled
+fn default_enabled_protocols() -> Vec<String> {
+    vec![
+        FileTransferProtocol::Sftp.to_string(),
+        FileTransferProtocol::Scp.to_string(),
+        FileTransferProtocol::Ftp(false).to_string(),
+        FileTransferProtocol::Ftp(true).to_string(),
+    ]
+}
+
+/// ### default_transfer_buffer_kb
+///
+/// Default size of the transfer buffer, in KB
+fn default_transfer_buffer_kb() -> u64 {
+    64
+}
+
 // Errors
 
 /// ## SerializerError
@@ -134,6 +247,13 @@ impl SerializerError {
         err.msg = Some(msg);
         err
     }
+
+    /// ### kind
+    ///
+    /// Get error kind
+    pub fn kind(&self) -> &SerializerErrorKind {
+        &self.kind
+    }
 }
 
 impl std::fmt::Display for SerializerError {
@@ -165,16 +285,38 @@ mod tests {
             String::from("192.168.1.31"),
             PathBuf::from("/tmp/private.key"),
         );
-        let remote: RemoteConfig = RemoteConfig { ssh_keys: keys };
+        let remote: RemoteConfig = RemoteConfig {
+            transfer_buffer_kb: 128,
+            save_passwords: false,
+            always_ask_credentials: true,
+            ssh_keys: keys,
+        };
         let ui: UserInterfaceConfig = UserInterfaceConfig {
             default_protocol: String::from("SFTP"),
             text_editor: PathBuf::from("nano"),
-            show_hidden_files: true,
+            show_hidden_files: None,
+            show_hidden_local: true,
+            show_hidden_remote: false,
             group_dirs: Some(String::from("first")),
+            confirm_remote_delete: false,
+            password_prompt_timeout_secs: Some(60),
+            temp_dir: Some(PathBuf::from("/mnt/ramdisk")),
+            notify_on_complete: true,
+            min_terminal: (100, 30),
+            enabled_protocols: vec![String::from("SFTP"), String::from("SCP")],
+            protocol_editors: HashMap::new(),
+            protocol_icons: HashMap::new(),
+        };
+        let recents: RecentsConfig = RecentsConfig {
+            max: Some(32),
+            max_age: Some(86400),
+            store_username: false,
         };
         let cfg: UserConfig = UserConfig {
+            session_tag: Some(String::from("CHG-1234")),
             user_interface: ui,
             remote: remote,
+            recents,
         };
         assert_eq!(
             *cfg.remote
@@ -185,8 +327,28 @@ mod tests {
         );
         assert_eq!(cfg.user_interface.default_protocol, String::from("SFTP"));
         assert_eq!(cfg.user_interface.text_editor, PathBuf::from("nano"));
-        assert_eq!(cfg.user_interface.show_hidden_files, true);
+        assert!(cfg.user_interface.show_hidden_local);
+        assert!(!cfg.user_interface.show_hidden_remote);
         assert_eq!(cfg.user_interface.group_dirs, Some(String::from("first")));
+        assert!(!cfg.user_interface.confirm_remote_delete);
+        assert_eq!(cfg.user_interface.password_prompt_timeout_secs, Some(60));
+        assert_eq!(
+            cfg.user_interface.temp_dir,
+            Some(PathBuf::from("/mnt/ramdisk"))
+        );
+        assert!(cfg.user_interface.notify_on_complete);
+        assert_eq!(cfg.user_interface.min_terminal, (100, 30));
+        assert_eq!(
+            cfg.user_interface.enabled_protocols,
+            vec![String::from("SFTP"), String::from("SCP")]
+        );
+        assert_eq!(cfg.remote.transfer_buffer_kb, 128);
+        assert!(!cfg.remote.save_passwords);
+        assert!(cfg.remote.always_ask_credentials);
+        assert_eq!(cfg.recents.max, Some(32));
+        assert_eq!(cfg.recents.max_age, Some(86400));
+        assert!(!cfg.recents.store_username);
+        assert_eq!(cfg.session_tag.as_deref(), Some("CHG-1234"));
     }
 
     #[test]
@@ -197,7 +359,22 @@ mod tests {
         let cfg: UserConfig = UserConfig::default();
         assert_eq!(cfg.user_interface.default_protocol, String::from("SFTP"));
         assert_eq!(cfg.user_interface.text_editor, PathBuf::from("vim"));
+        assert_eq!(cfg.user_interface.protocol_editors.len(), 0);
+        assert_eq!(cfg.user_interface.protocol_icons.len(), 0);
+        assert_eq!(cfg.user_interface.enabled_protocols.len(), 4);
+        assert!(cfg.user_interface.confirm_remote_delete);
+        assert_eq!(cfg.user_interface.password_prompt_timeout_secs, None);
+        assert!(cfg.user_interface.temp_dir.is_none());
+        assert!(!cfg.user_interface.notify_on_complete);
+        assert_eq!(cfg.user_interface.min_terminal, (80, 24));
         assert_eq!(cfg.remote.ssh_keys.len(), 0);
+        assert_eq!(cfg.remote.transfer_buffer_kb, 64);
+        assert!(cfg.remote.save_passwords);
+        assert!(!cfg.remote.always_ask_credentials);
+        assert_eq!(cfg.recents.max, Some(16));
+        assert_eq!(cfg.recents.max_age, None);
+        assert!(cfg.recents.store_username);
+        assert!(cfg.session_tag.is_none());
     }
 
     #[test]
@@ -39,15 +39,7 @@ impl ConfigSerializer {
         cfg: &UserConfig,
     ) -> Result<(), SerializerError> {
         // Serialize content
-        let data: String = match toml::ser::to_string(cfg) {
-            Ok(dt) => dt,
-            Err(err) => {
-                return Err(SerializerError::new_ex(
-                    SerializerErrorKind::SerializationError,
-                    err.to_string(),
-                ))
-            }
-        };
+        let data: String = self.serialize_to_string(cfg)?;
         // Write file
         match writable.write_all(data.as_bytes()) {
             Ok(_) => Ok(()),
@@ -58,6 +50,19 @@ impl ConfigSerializer {
         }
     }
 
+    /// ### serialize_to_string
+    ///
+    /// Serialize `UserConfig` into a TOML string
+    pub fn serialize_to_string(&self, cfg: &UserConfig) -> Result<String, SerializerError> {
+        match toml::ser::to_string(cfg) {
+            Ok(dt) => Ok(dt),
+            Err(err) => Err(SerializerError::new_ex(
+                SerializerErrorKind::SerializationError,
+                err.to_string(),
+            )),
+        }
+    }
+
     /// ### deserialize
     ///
     /// Read data from readable and deserialize its content as TOML
@@ -71,16 +76,50 @@ impl ConfigSerializer {
             ));
         }
         // Deserialize
-        match toml::de::from_str(data.as_str()) {
-            Ok(hosts) => Ok(hosts),
+        self.deserialize_from_str(data.as_str())
+    }
+
+    /// ### deserialize_from_str
+    ///
+    /// Deserialize TOML string into `UserConfig`
+    pub fn deserialize_from_str(&self, data: &str) -> Result<UserConfig, SerializerError> {
+        match toml::de::from_str(data) {
+            Ok(config) => {
+                let mut config: UserConfig = config;
+                config.migrate();
+                Ok(config)
+            }
             Err(err) => Err(SerializerError::new_ex(
                 SerializerErrorKind::SyntaxError,
-                err.to_string(),
+                fmt_toml_error(&err),
             )),
         }
     }
 }
 
+/// ### fmt_toml_error
+///
+/// Format TOML deserializer error, putting the position of the error (if known) in front of the description,
+/// e.g. `line 3, column 15: invalid number`
+fn fmt_toml_error(err: &toml::de::Error) -> String {
+    let description: String = err.to_string();
+    match err.line_col() {
+        Some((line, col)) => {
+            // Strip the position from the description, since it's already reported
+            let position: String = format!(" at line {} column {}", line + 1, col + 1);
+            format!(
+                "line {}, column {}: {}",
+                line + 1,
+                col + 1,
+                description
+                    .strip_suffix(position.as_str())
+                    .unwrap_or(description.as_str())
+            )
+        }
+        None => description,
+    }
+}
+
 // Tests
 
 #[cfg(test)]
@@ -103,11 +142,47 @@ mod tests {
         assert!(cfg.is_ok());
         let cfg: UserConfig = cfg.ok().unwrap();
         // Verify configuration
+        assert_eq!(cfg.session_tag.as_deref(), Some("CHG-1234"));
         // Verify ui
         assert_eq!(cfg.user_interface.default_protocol, String::from("SCP"));
         assert_eq!(cfg.user_interface.text_editor, PathBuf::from("vim"));
-        assert_eq!(cfg.user_interface.show_hidden_files, true);
+        assert!(cfg.user_interface.show_hidden_local);
+        assert!(!cfg.user_interface.show_hidden_remote);
         assert_eq!(cfg.user_interface.group_dirs, Some(String::from("last")));
+        assert!(!cfg.user_interface.confirm_remote_delete);
+        assert_eq!(cfg.user_interface.password_prompt_timeout_secs, Some(30));
+        assert_eq!(
+            cfg.user_interface.temp_dir,
+            Some(PathBuf::from("/mnt/ramdisk"))
+        );
+        assert!(cfg.user_interface.notify_on_complete);
+        assert_eq!(cfg.user_interface.min_terminal, (120, 40));
+        assert_eq!(
+            cfg.user_interface.enabled_protocols,
+            vec![String::from("SFTP"), String::from("FTPS")]
+        );
+        // Verify recents
+        assert_eq!(cfg.recents.max, Some(32));
+        assert_eq!(cfg.recents.max_age, Some(86400));
+        assert!(!cfg.recents.store_username);
+        assert_eq!(
+            *cfg.user_interface
+                .protocol_editors
+                .get(&String::from("SFTP"))
+                .unwrap(),
+            PathBuf::from("code")
+        );
+        assert_eq!(
+            cfg.user_interface
+                .protocol_icons
+                .get("SFTP")
+                .map(|x| x.as_str()),
+            Some("🔒")
+        );
+        // Verify remote
+        assert_eq!(cfg.remote.transfer_buffer_kb, 128);
+        assert!(!cfg.remote.save_passwords);
+        assert!(cfg.remote.always_ask_credentials);
         // Verify keys
         assert_eq!(
             *cfg.remote
@@ -141,8 +216,27 @@ mod tests {
         // Verify ui
         assert_eq!(cfg.user_interface.default_protocol, String::from("SCP"));
         assert_eq!(cfg.user_interface.text_editor, PathBuf::from("vim"));
-        assert_eq!(cfg.user_interface.show_hidden_files, true);
+        // Migrated from `show_hidden_files`
+        assert_eq!(cfg.user_interface.show_hidden_files, None);
+        assert!(cfg.user_interface.show_hidden_local);
+        assert!(cfg.user_interface.show_hidden_remote);
         assert_eq!(cfg.user_interface.group_dirs, None);
+        assert!(cfg.user_interface.confirm_remote_delete);
+        assert_eq!(cfg.user_interface.password_prompt_timeout_secs, None);
+        assert!(cfg.user_interface.temp_dir.is_none());
+        assert!(!cfg.user_interface.notify_on_complete);
+        assert_eq!(cfg.user_interface.min_terminal, (80, 24));
+        assert_eq!(cfg.user_interface.enabled_protocols.len(), 4);
+        // Verify recents (default)
+        assert_eq!(cfg.recents.max, Some(16));
+        assert_eq!(cfg.recents.max_age, None);
+        assert!(cfg.recents.store_username);
+        assert_eq!(cfg.user_interface.protocol_editors.len(), 0);
+        assert_eq!(cfg.user_interface.protocol_icons.len(), 0);
+        // Verify remote (default)
+        assert_eq!(cfg.remote.transfer_buffer_kb, 64);
+        assert!(cfg.remote.save_passwords);
+        assert!(!cfg.remote.always_ask_credentials);
         // Verify keys
         assert_eq!(
             *cfg.remote
@@ -171,10 +265,32 @@ mod tests {
         assert!(deserializer.deserialize(Box::new(toml_file)).is_err());
     }
 
+    #[test]
+    fn test_config_serializer_deserialize_nok_position() {
+        let mut toml_file: tempfile::NamedTempFile = tempfile::NamedTempFile::new().unwrap();
+        // Unquoted string at line 4
+        let file_content: &str = "[user_interface]\ndefault_protocol = \"SFTP\"\nshow_hidden_files = true\ntext_editor = vim\n";
+        toml_file.write_all(file_content.as_bytes()).unwrap();
+        toml_file.as_file().sync_all().unwrap();
+        toml_file.as_file().seek(SeekFrom::Start(0)).unwrap();
+        // Parse
+        let deserializer: ConfigSerializer = ConfigSerializer {};
+        let err: String = deserializer
+            .deserialize(Box::new(toml_file))
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("line 4, column"), "{}", err);
+    }
+
     #[test]
     fn test_config_serializer_serialize() {
         let mut cfg: UserConfig = UserConfig::default();
         let toml_file: tempfile::NamedTempFile = tempfile::NamedTempFile::new().ok().unwrap();
+        // Insert protocol editor
+        cfg.user_interface
+            .protocol_editors
+            .insert(String::from("SCP"), PathBuf::from("vim"));
         // Insert key
         cfg.remote.ssh_keys.insert(
             String::from("192.168.1.31"),
@@ -194,15 +310,40 @@ mod tests {
         // Write
         let mut tmpfile: tempfile::NamedTempFile = tempfile::NamedTempFile::new().unwrap();
         let file_content: &str = r#"
+        session_tag = "CHG-1234"
+
         [user_interface]
         default_protocol = "SCP"
         text_editor = "vim"
-        show_hidden_files = true
         group_dirs = "last"
+        show_hidden_local = true
+        show_hidden_remote = false
+        confirm_remote_delete = false
+        password_prompt_timeout_secs = 30
+        temp_dir = "/mnt/ramdisk"
+        notify_on_complete = true
+        min_terminal = [120, 40]
+        enabled_protocols = ["SFTP", "FTPS"]
+
+        [user_interface.protocol_editors]
+        SFTP = "code"
+
+        [user_interface.protocol_icons]
+        SFTP = "🔒"
+
+        [remote]
+        transfer_buffer_kb = 128
+        save_passwords = false
+        always_ask_credentials = true
 
         [remote.ssh_keys]
         "192.168.1.31" = "/home/omar/.ssh/raspberry.key"
         "192.168.1.32" = "/home/omar/.ssh/beaglebone.key"
+
+        [recents]
+        max = 32
+        max_age = 86400
+        store_username = false
         "#;
         tmpfile.write_all(file_content.as_bytes()).unwrap();
         tmpfile
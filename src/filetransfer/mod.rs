@@ -44,6 +44,33 @@ pub enum FileTransferProtocol {
     Ftp(bool), // Bool is for secure (true => ftps)
 }
 
+impl FileTransferProtocol {
+    /// ### scheme
+    ///
+    /// Get the URI scheme for protocol (e.g. `sftp`)
+    pub fn scheme(&self) -> &'static str {
+        match self {
+            FileTransferProtocol::Ftp(true) => "ftps",
+            FileTransferProtocol::Ftp(false) => "ftp",
+            FileTransferProtocol::Scp => "scp",
+            FileTransferProtocol::Sftp => "sftp",
+        }
+    }
+
+    /// ### guess_from_port
+    ///
+    /// Guess protocol from the well-known port it's usually served on.
+    /// SSH ports are reported as SFTP, since SCP uses the same ports
+    pub fn guess_from_port(port: u16) -> Option<FileTransferProtocol> {
+        match port {
+            22 | 2222 => Some(FileTransferProtocol::Sftp),
+            21 => Some(FileTransferProtocol::Ftp(false)),
+            990 => Some(FileTransferProtocol::Ftp(true)),
+            _ => None,
+        }
+    }
+}
+
 /// ## FileTransferError
 ///
 /// FileTransferError defines the possible errors available for a file transfer
@@ -322,6 +349,35 @@ mod tests {
         assert_eq!(FileTransferProtocol::Sftp.to_string(), String::from("SFTP"));
     }
 
+    #[test]
+    fn test_filetransfer_mod_protocol_scheme() {
+        assert_eq!(FileTransferProtocol::Ftp(true).scheme(), "ftps");
+        assert_eq!(FileTransferProtocol::Ftp(false).scheme(), "ftp");
+        assert_eq!(FileTransferProtocol::Scp.scheme(), "scp");
+        assert_eq!(FileTransferProtocol::Sftp.scheme(), "sftp");
+    }
+
+    #[test]
+    fn test_filetransfer_mod_protocol_guess_from_port() {
+        assert_eq!(
+            FileTransferProtocol::guess_from_port(22),
+            Some(FileTransferProtocol::Sftp)
+        );
+        assert_eq!(
+            FileTransferProtocol::guess_from_port(2222),
+            Some(FileTransferProtocol::Sftp)
+        );
+        assert_eq!(
+            FileTransferProtocol::guess_from_port(21),
+            Some(FileTransferProtocol::Ftp(false))
+        );
+        assert_eq!(
+            FileTransferProtocol::guess_from_port(990),
+            Some(FileTransferProtocol::Ftp(true))
+        );
+        assert_eq!(FileTransferProtocol::guess_from_port(8080), None);
+    }
+
     #[test]
     fn test_filetransfer_mod_error() {
         let err: FileTransferError = FileTransferError::new_ex(
@@ -30,12 +30,19 @@ extern crate bitflags;
 extern crate lazy_static;
 #[macro_use]
 extern crate magic_crypt;
+#[cfg(target_family = "unix")]
+extern crate libc;
 extern crate rpassword;
 
 // External libs
-use getopts::Options;
+use getopts::{Matches, Options};
 use std::env;
-use std::path::PathBuf;
+use std::fs::File;
+#[cfg(target_family = "unix")]
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
 use std::time::Duration;
 
 // Include
@@ -52,6 +59,13 @@ mod utils;
 // namespaces
 use activity_manager::{ActivityManager, NextActivity};
 use filetransfer::FileTransferProtocol;
+use system::backup;
+use system::bookmarks_client::{BookmarksClient, ConflictPolicy, ImportReport};
+use system::config_client::ConfigClient;
+#[cfg(feature = "with-keyring")]
+use system::credential_store::KeyringStore;
+use system::environment;
+use system::sshkey_storage::SshKeyStorage;
 
 /// ### print_usage
 ///
@@ -63,6 +77,228 @@ fn print_usage(opts: Options) {
     println!("\nPlease, report issues to <https://github.com/veeso/termscp>");
 }
 
+/// ### get_password_prompt_timeout
+///
+/// Get password prompt timeout from user configuration
+fn get_password_prompt_timeout() -> Option<Duration> {
+    let config_dir: PathBuf = environment::init_config_dir().ok()??;
+    let (config_path, ssh_dir): (PathBuf, PathBuf) =
+        environment::get_config_paths(config_dir.as_path());
+    let config_cli: ConfigClient =
+        ConfigClient::new(config_path.as_path(), ssh_dir.as_path()).ok()?;
+    config_cli
+        .get_password_prompt_timeout_secs()
+        .map(Duration::from_secs)
+}
+
+/// ## TtyAttributes
+///
+/// Attributes of the controlling terminal, saved before prompting for password
+#[cfg(target_family = "unix")]
+struct TtyAttributes {
+    tty: std::fs::File,
+    termios: libc::termios,
+}
+
+#[cfg(target_family = "unix")]
+impl TtyAttributes {
+    /// ### save
+    ///
+    /// Read current attributes of the controlling terminal; `None` if there's no terminal
+    fn save() -> Option<TtyAttributes> {
+        let tty: std::fs::File = std::fs::File::open("/dev/tty").ok()?;
+        let mut termios: libc::termios = unsafe { std::mem::zeroed() };
+        match unsafe { libc::tcgetattr(tty.as_raw_fd(), &mut termios) } {
+            0 => Some(TtyAttributes { tty, termios }),
+            _ => None,
+        }
+    }
+
+    /// ### restore
+    ///
+    /// Restore saved attributes of the controlling terminal
+    fn restore(&self) {
+        unsafe {
+            libc::tcsetattr(self.tty.as_raw_fd(), libc::TCSANOW, &self.termios);
+        }
+    }
+}
+
+/// ### read_password
+///
+/// Read password from tty, showing `prompt`. If timeout is provided and the user doesn't submit the password
+/// before it elapses, the prompt is cancelled.
+/// NOTE: the prompt disables echo and can't be interrupted, so on timeout it's left running in its thread;
+/// on Unix terminal attributes are restored before returning, otherwise the shell would be left without echo
+fn read_password(prompt: &str, timeout: Option<Duration>) -> Result<String, String> {
+    #[cfg(target_family = "unix")]
+    let tty: Option<TtyAttributes> = TtyAttributes::save();
+    let (tx, rx) = mpsc::channel();
+    let prompt: String = prompt.to_string();
+    thread::spawn(move || {
+        let _ = tx.send(rpassword::read_password_from_tty(Some(prompt.as_str())));
+    });
+    let result: std::io::Result<String> = match timeout {
+        Some(timeout) => match rx.recv_timeout(timeout) {
+            Ok(result) => result,
+            Err(_) => {
+                #[cfg(target_family = "unix")]
+                if let Some(tty) = tty.as_ref() {
+                    tty.restore();
+                }
+                // Prompt line has not been terminated by the user
+                eprintln!();
+                return Err(String::from("Password prompt timed out"));
+            }
+        },
+        None => match rx.recv() {
+            Ok(result) => result,
+            Err(err) => return Err(err.to_string()),
+        },
+    };
+    result.map_err(|_| String::from("Could not read password from prompt"))
+}
+
+/// ### init_clients
+///
+/// Initialize configuration and bookmarks clients from the configuration directory
+fn init_clients() -> Result<(ConfigClient, BookmarksClient), String> {
+    let config_dir: PathBuf = match environment::init_config_dir() {
+        Ok(Some(dir)) => dir,
+        Ok(None) => return Err(String::from("Configuration directory is not available")),
+        Err(err) => {
+            return Err(format!(
+                "Could not initialize configuration directory: {}",
+                err
+            ))
+        }
+    };
+    let (config_path, ssh_dir): (PathBuf, PathBuf) =
+        environment::get_config_paths(config_dir.as_path());
+    let config_cli: ConfigClient = ConfigClient::new(config_path.as_path(), ssh_dir.as_path())
+        .map_err(|err| format!("Could not initialize user configuration: {}", err))?;
+    let (bookmarks_file, key_file): (PathBuf, PathBuf) =
+        environment::get_bookmarks_paths(config_dir.as_path());
+    let mut bookmarks_cli: BookmarksClient =
+        BookmarksClient::new(bookmarks_file.as_path(), key_file.as_path(), 16)
+            .map_err(|err| format!("Could not initialize bookmarks: {}", err))?;
+    bookmarks_cli.set_sanitize_names(true);
+    // Keep passwords in the system keyring
+    #[cfg(feature = "with-keyring")]
+    bookmarks_cli.set_credential_store(Box::new(KeyringStore::new("termscp")));
+    Ok((config_cli, bookmarks_cli))
+}
+
+/// ### read_passphrase
+///
+/// Read passphrase of a backup archive from tty
+fn read_passphrase() -> Result<String, String> {
+    match read_password("Passphrase: ", None)? {
+        passphrase if passphrase.is_empty() => Err(String::from("Passphrase can't be empty")),
+        passphrase => Ok(passphrase),
+    }
+}
+
+/// ### run_bookmarks_command
+///
+/// Run the bookmarks command requested with CLI options; returns `None` if no command has been requested
+fn run_bookmarks_command(matches: &Matches) -> Option<Result<(), String>> {
+    let commands: [&str; 5] = [
+        "import-bookmarks",
+        "export-ssh-config",
+        "backup",
+        "restore",
+        "encrypt-bookmarks",
+    ];
+    if !commands.iter().any(|x| matches.opt_present(x)) {
+        return None;
+    }
+    let (mut config_cli, mut bookmarks_cli): (ConfigClient, BookmarksClient) = match init_clients()
+    {
+        Ok(clients) => clients,
+        Err(err) => return Some(Err(err)),
+    };
+    Some(if let Some(path) = matches.opt_str("import-bookmarks") {
+        let policy: ConflictPolicy = match matches.opt_present("overwrite") {
+            true => ConflictPolicy::Overwrite,
+            false => ConflictPolicy::Skip,
+        };
+        import_bookmarks(&mut bookmarks_cli, Path::new(path.as_str()), policy)
+    } else if let Some(path) = matches.opt_str("export-ssh-config") {
+        File::create(path.as_str())
+            .map_err(|err| format!("Could not open \"{}\": {}", path, err))
+            .and_then(|file| {
+                bookmarks_cli
+                    .export_ssh_config(
+                        Box::new(file),
+                        &SshKeyStorage::storage_from_config(&config_cli),
+                    )
+                    .map_err(|err| format!("Could not export bookmarks: {}", err))
+            })
+    } else if let Some(path) = matches.opt_str("backup") {
+        read_passphrase().and_then(|passphrase| {
+            let file: File = File::create(path.as_str())
+                .map_err(|err| format!("Could not open \"{}\": {}", path, err))?;
+            backup::export_backup(&config_cli, &bookmarks_cli, Box::new(file), &passphrase)
+                .map_err(|err| format!("Could not write backup: {}", err))
+        })
+    } else if let Some(path) = matches.opt_str("restore") {
+        File::open(path.as_str())
+            .map_err(|err| format!("Could not open \"{}\": {}", path, err))
+            .and_then(|file| {
+                let passphrase: String = read_passphrase()?;
+                backup::import_backup(
+                    &mut config_cli,
+                    &mut bookmarks_cli,
+                    Box::new(file),
+                    &passphrase,
+                )
+                .map_err(|err| format!("Could not restore backup: {}", err))
+            })
+    } else if bookmarks_cli.is_full_file_encryption() {
+        println!("Bookmarks file is already encrypted");
+        Ok(())
+    } else {
+        bookmarks_cli
+            .migrate_to_full_file_encryption()
+            .map_err(|err| format!("Could not encrypt bookmarks: {}", err))
+    })
+}
+
+/// ### import_bookmarks
+///
+/// Import bookmarks from the CSV file at `path`, then print a summary of the import
+fn import_bookmarks(
+    bookmarks_cli: &mut BookmarksClient,
+    path: &Path,
+    policy: ConflictPolicy,
+) -> Result<(), String> {
+    let file: File = File::open(path)
+        .map_err(|err| format!("Could not open \"{}\": {}", path.display(), err))?;
+    let report: ImportReport = bookmarks_cli
+        .import_bookmarks_csv(Box::new(file), policy, false)
+        .map_err(|err| format!("Could not import bookmarks: {}", err))?;
+    for (name, sanitized) in report.renamed.iter() {
+        println!("\"{}\" has been imported as \"{}\"", name, sanitized);
+    }
+    for err in report.errors.iter() {
+        eprintln!("{}", err);
+    }
+    println!(
+        "{} added, {} overwritten, {} skipped, {} errors",
+        report.added.len(),
+        report.overwritten.len(),
+        report.skipped.len(),
+        report.errors.len()
+    );
+    if report.added.is_empty() && report.overwritten.is_empty() {
+        return Ok(());
+    }
+    bookmarks_cli
+        .write_bookmarks()
+        .map_err(|err| format!("Could not write bookmarks: {}", err))
+}
+
 fn main() {
     let args: Vec<String> = env::args().collect();
     //Program CLI options
@@ -81,6 +317,40 @@ fn main() {
         "<password>",
     );
     opts.optopt("T", "ticks", "Set UI ticks; default 10ms", "<ms>");
+    opts.optopt(
+        "",
+        "import-bookmarks",
+        "Import bookmarks from a CSV file (name,address,port,protocol,username[,password])",
+        "<file>",
+    );
+    opts.optflag(
+        "",
+        "overwrite",
+        "Replace existing bookmarks with the imported ones",
+    );
+    opts.optopt(
+        "",
+        "export-ssh-config",
+        "Export SFTP/SCP bookmarks as an SSH config file",
+        "<file>",
+    );
+    opts.optopt(
+        "",
+        "backup",
+        "Write configuration, bookmarks and SSH keys to an archive encrypted with a passphrase",
+        "<file>",
+    );
+    opts.optopt(
+        "",
+        "restore",
+        "Restore an archive written with --backup",
+        "<file>",
+    );
+    opts.optflag(
+        "",
+        "encrypt-bookmarks",
+        "Encrypt the whole bookmarks file, rather than passwords only",
+    );
     opts.optflag("v", "version", "");
     opts.optflag("h", "help", "Print this menu");
     let matches = match opts.parse(&args[1..]) {
@@ -118,6 +388,16 @@ fn main() {
             }
         }
     }
+    // Bookmarks commands
+    if let Some(result) = run_bookmarks_command(&matches) {
+        match result {
+            Ok(()) => std::process::exit(0),
+            Err(err) => {
+                eprintln!("{}", err);
+                std::process::exit(255);
+            }
+        }
+    }
     // Check free args
     let extra_args: Vec<String> = matches.free;
     if let Some(remote) = extra_args.get(0) {
@@ -147,7 +427,7 @@ fn main() {
     if address.is_some() {
         if password.is_none() {
             // Ask password if unspecified
-            password = match rpassword::read_password_from_tty(Some("Password: ")) {
+            password = match read_password("Password: ", get_password_prompt_timeout()) {
                 Ok(p) => {
                     if p.is_empty() {
                         None
@@ -155,8 +435,8 @@ fn main() {
                         Some(p)
                     }
                 }
-                Err(_) => {
-                    eprintln!("Could not read password from prompt");
+                Err(err) => {
+                    eprintln!("{}", err);
                     std::process::exit(255);
                 }
             };
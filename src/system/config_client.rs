@@ -26,27 +26,44 @@
 // Deps
 extern crate rand;
 // Locals
+use crate::bookmarks::RecentsPolicy;
 use crate::config::serializer::ConfigSerializer;
 use crate::config::{SerializerError, SerializerErrorKind, UserConfig};
 use crate::filetransfer::FileTransferProtocol;
 use crate::fs::explorer::GroupDirs;
 // Ext
-use std::fs::{create_dir, remove_file, File, OpenOptions};
+use std::ffi::OsString;
+use std::fs::{create_dir, remove_file, rename, File, OpenOptions};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::string::ToString;
+use std::time::Duration;
 
 // Types
 pub type SshHost = (String, String, PathBuf); // 0: host, 1: username, 2: RSA key path
 
+// Transfer buffer range (KB)
+pub const TRANSFER_BUFFER_KB_MIN: u64 = 4;
+pub const TRANSFER_BUFFER_KB_MAX: u64 = 8192;
+
+/// ## RecoveryMode
+///
+/// Describes what to do when the configuration file can't be parsed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RecoveryMode {
+    Fail,   // Return error
+    Backup, // Move configuration file to `<name>.corrupt`, then write the default configuration
+}
+
 /// ## ConfigClient
 ///
 /// ConfigClient provides a high level API to communicate with the termscp configuration
 pub struct ConfigClient {
-    config: UserConfig,   // Configuration loaded
-    config_path: PathBuf, // Configuration TOML Path
-    ssh_key_dir: PathBuf, // SSH Key storage directory
+    config: UserConfig,        // Configuration loaded
+    config_path: PathBuf,      // Configuration TOML Path
+    ssh_key_dir: PathBuf,      // SSH Key storage directory
+    recovered: Option<String>, // Describes how a corrupted configuration has been recovered
 }
 
 impl ConfigClient {
@@ -54,6 +71,18 @@ impl ConfigClient {
     ///
     /// Instantiate a new `ConfigClient` with provided path
     pub fn new(config_path: &Path, ssh_key_dir: &Path) -> Result<ConfigClient, SerializerError> {
+        Self::new_with_recovery(config_path, ssh_key_dir, RecoveryMode::Fail)
+    }
+
+    /// ### new_with_recovery
+    ///
+    /// Instantiate a new `ConfigClient` with provided path.
+    /// If the configuration file can't be parsed, it is handled as described by `recovery`
+    pub fn new_with_recovery(
+        config_path: &Path,
+        ssh_key_dir: &Path,
+        recovery: RecoveryMode,
+    ) -> Result<ConfigClient, SerializerError> {
         // Initialize a default configuration
         let default_config: UserConfig = UserConfig::default();
         // Create client
@@ -61,6 +90,7 @@ impl ConfigClient {
             config: default_config,
             config_path: PathBuf::from(config_path),
             ssh_key_dir: PathBuf::from(ssh_key_dir),
+            recovered: None,
         };
         // If ssh key directory doesn't exist, create it
         if !ssh_key_dir.exists() {
@@ -74,6 +104,23 @@ impl ConfigClient {
                     ),
                 ));
             }
+            // Only the owner can access SSH keys
+            #[cfg(target_family = "unix")]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                if let Err(err) =
+                    std::fs::set_permissions(ssh_key_dir, std::fs::Permissions::from_mode(0o700))
+                {
+                    return Err(SerializerError::new_ex(
+                        SerializerErrorKind::IoError,
+                        format!(
+                            "Could not set permissions of SSH key directory \"{}\": {}",
+                            ssh_key_dir.display(),
+                            err
+                        ),
+                    ));
+                }
+            }
         }
         // If Config file doesn't exist, create it
         if !config_path.exists() {
@@ -83,12 +130,55 @@ impl ConfigClient {
         } else {
             // otherwise Load configuration from file
             if let Err(err) = client.read_config() {
-                return Err(err);
+                // Only syntax errors can be recovered
+                if *err.kind() != SerializerErrorKind::SyntaxError {
+                    return Err(err);
+                }
+                client.recover(recovery, err)?;
             }
         }
         Ok(client)
     }
 
+    /// ### recover
+    ///
+    /// Recover from a corrupted configuration file, replacing it with the default configuration
+    fn recover(
+        &mut self,
+        recovery: RecoveryMode,
+        err: SerializerError,
+    ) -> Result<(), SerializerError> {
+        self.config = UserConfig::default();
+        match recovery {
+            RecoveryMode::Fail => return Err(err),
+            RecoveryMode::Backup => {
+                let mut backup_name: OsString = self
+                    .config_path
+                    .file_name()
+                    .map(|x| x.to_os_string())
+                    .unwrap_or_default();
+                backup_name.push(".corrupt");
+                let backup_path: PathBuf = self.config_path.with_file_name(backup_name);
+                if let Err(err) = rename(self.config_path.as_path(), backup_path.as_path()) {
+                    return Err(SerializerError::new_ex(
+                        SerializerErrorKind::IoError,
+                        format!(
+                            "Could not move corrupted configuration to \"{}\": {}",
+                            backup_path.display(),
+                            err
+                        ),
+                    ));
+                }
+                self.recovered = Some(format!(
+                    "Configuration file was corrupted ({}); it has been moved to \"{}\" and default configuration has been restored",
+                    err,
+                    backup_path.display()
+                ));
+            }
+        }
+        self.write_config()
+    }
+
     // Text editor
 
     /// ### get_text_editor
@@ -105,6 +195,33 @@ impl ConfigClient {
         self.config.user_interface.text_editor = path;
     }
 
+    /// ### get_editor_for
+    ///
+    /// Get text editor to use for files on a remote using the provided protocol.
+    /// If no editor is set for the protocol, `text_editor` is returned
+    pub fn get_editor_for(&self, proto: FileTransferProtocol) -> PathBuf {
+        match self
+            .config
+            .user_interface
+            .protocol_editors
+            .get(&proto.to_string())
+        {
+            Some(editor) => editor.clone(),
+            None => self.get_text_editor(),
+        }
+    }
+
+    /// ### get_protocol_icon
+    ///
+    /// Get icon to display beside connections using the provided protocol
+    pub fn get_protocol_icon(&self, proto: FileTransferProtocol) -> Option<&str> {
+        self.config
+            .user_interface
+            .protocol_icons
+            .get(&proto.to_string())
+            .map(|x| x.as_str())
+    }
+
     // Default protocol
 
     /// ### get_default_protocol
@@ -126,16 +243,90 @@ impl ConfigClient {
 
     /// ### get_show_hidden_files
     ///
-    /// Get value of `show_hidden_files`
+    /// Get whether hidden files are shown in both local and remote explorers
     pub fn get_show_hidden_files(&self) -> bool {
-        self.config.user_interface.show_hidden_files
+        self.config.user_interface.show_hidden_local
+            && self.config.user_interface.show_hidden_remote
     }
 
     /// ### set_show_hidden_files
     ///
-    /// Set new value for `show_hidden_files`
+    /// Set whether hidden files are shown in both local and remote explorers
     pub fn set_show_hidden_files(&mut self, value: bool) {
-        self.config.user_interface.show_hidden_files = value;
+        self.config.user_interface.show_hidden_local = value;
+        self.config.user_interface.show_hidden_remote = value;
+    }
+
+    /// ### get_show_hidden_local
+    ///
+    /// Get value of `show_hidden_local`
+    pub fn get_show_hidden_local(&self) -> bool {
+        self.config.user_interface.show_hidden_local
+    }
+
+    /// ### get_show_hidden_remote
+    ///
+    /// Get value of `show_hidden_remote`
+    pub fn get_show_hidden_remote(&self) -> bool {
+        self.config.user_interface.show_hidden_remote
+    }
+
+    /// ### get_confirm_remote_delete
+    ///
+    /// Get value of `confirm_remote_delete`
+    pub fn get_confirm_remote_delete(&self) -> bool {
+        self.config.user_interface.confirm_remote_delete
+    }
+
+    /// ### get_password_prompt_timeout_secs
+    ///
+    /// Get timeout in seconds for password prompt; `None` if prompt never times out
+    pub fn get_password_prompt_timeout_secs(&self) -> Option<u64> {
+        self.config.user_interface.password_prompt_timeout_secs
+    }
+
+    /// ### get_temp_dir
+    ///
+    /// Get directory where temporary files are created; `None` if system's temporary directory is used
+    pub fn get_temp_dir(&self) -> Option<PathBuf> {
+        self.config.user_interface.temp_dir.clone()
+    }
+
+    /// ### get_notify_on_complete
+    ///
+    /// Get value of `notify_on_complete`
+    pub fn get_notify_on_complete(&self) -> bool {
+        self.config.user_interface.notify_on_complete
+    }
+
+    /// ### get_min_terminal
+    ///
+    /// Get minimum terminal size as (columns, rows)
+    pub fn get_min_terminal(&self) -> (u16, u16) {
+        self.config.user_interface.min_terminal
+    }
+
+    /// ### get_enabled_protocols
+    ///
+    /// Get protocols to show in the connect form. Unknown protocols are ignored;
+    /// if no valid protocol is enabled, all the protocols are returned
+    pub fn get_enabled_protocols(&self) -> Vec<FileTransferProtocol> {
+        let protocols: Vec<FileTransferProtocol> = self
+            .config
+            .user_interface
+            .enabled_protocols
+            .iter()
+            .filter_map(|x| FileTransferProtocol::from_str(x.as_str()).ok())
+            .collect();
+        match protocols.is_empty() {
+            false => protocols,
+            true => vec![
+                FileTransferProtocol::Sftp,
+                FileTransferProtocol::Scp,
+                FileTransferProtocol::Ftp(false),
+                FileTransferProtocol::Ftp(true),
+            ],
+        }
     }
 
     /// ### get_group_dirs
@@ -163,6 +354,57 @@ impl ConfigClient {
         };
     }
 
+    // Recents
+
+    /// ### get_recents_policy
+    ///
+    /// Get policy to apply to recents
+    pub fn get_recents_policy(&self) -> RecentsPolicy {
+        RecentsPolicy {
+            max: self.config.recents.max,
+            max_age: self.config.recents.max_age.map(Duration::from_secs),
+        }
+    }
+
+    /// ### get_recents_store_username
+    ///
+    /// Get whether username must be stored in recents
+    pub fn get_recents_store_username(&self) -> bool {
+        self.config.recents.store_username
+    }
+
+    /// ### get_session_tag
+    ///
+    /// Get tag reported in session logs, for auditing. Empty tags are reported as `None`
+    pub fn get_session_tag(&self) -> Option<&str> {
+        self.config.session_tag.as_deref().filter(|x| !x.is_empty())
+    }
+
+    /// ### get_transfer_buffer_kb
+    ///
+    /// Get size of the buffer used to transfer files, in KB.
+    /// Size is clamped between `TRANSFER_BUFFER_KB_MIN` and `TRANSFER_BUFFER_KB_MAX`, since it may have been edited by hand
+    pub fn get_transfer_buffer_kb(&self) -> u64 {
+        self.config
+            .remote
+            .transfer_buffer_kb
+            .clamp(TRANSFER_BUFFER_KB_MIN, TRANSFER_BUFFER_KB_MAX)
+    }
+
+    /// ### get_save_passwords
+    ///
+    /// Get whether passwords can be saved with bookmarks
+    pub fn get_save_passwords(&self) -> bool {
+        self.config.remote.save_passwords
+    }
+
+    /// ### get_always_ask_credentials
+    ///
+    /// Get whether stored passwords must never be filled in automatically when loading a bookmark
+    pub fn get_always_ask_credentials(&self) -> bool {
+        self.config.remote.always_ask_credentials
+    }
+
     // SSH Keys
 
     /// ### save_ssh_key
@@ -246,8 +488,69 @@ impl ConfigClient {
         Box::new(self.config.remote.ssh_keys.keys())
     }
 
+    /// ### verify_ssh_keys
+    ///
+    /// Try to open for read each key file registered in the configuration.
+    /// Returns the hosts whose key file couldn't be read, with the error returned while opening it
+    pub fn verify_ssh_keys(&self) -> Vec<(String, std::io::Error)> {
+        let mut errors: Vec<(String, std::io::Error)> = Vec::new();
+        for (host, key_path) in self.config.remote.ssh_keys.iter() {
+            if let Err(err) = OpenOptions::new().read(true).open(key_path.as_path()) {
+                errors.push((host.clone(), err));
+            }
+        }
+        errors
+    }
+
+    /// ### config_warnings
+    ///
+    /// Check configuration for weak or broken settings and return an advisory message for each of them
+    pub fn config_warnings(&self) -> Vec<String> {
+        let mut warnings: Vec<String> = Vec::new();
+        if let Some(recovered) = self.recovered.as_ref() {
+            warnings.push(recovered.clone());
+        }
+        if self.get_default_protocol() == FileTransferProtocol::Ftp(false) {
+            warnings.push(String::from(
+                "FTP (insecure) set as default protocol; consider using SFTP or FTPS",
+            ));
+        }
+        for (host, err) in self.verify_ssh_keys().into_iter() {
+            warnings.push(format!("Could not read SSH key for {} ({})", host, err));
+        }
+        warnings
+    }
+
     // I/O
 
+    /// ### validate_toml
+    ///
+    /// Parse configuration from a TOML string, without reading or writing any file.
+    /// Returns the parsed configuration or the reason why it's not valid
+    pub fn validate_toml(s: &str) -> Result<UserConfig, SerializerError> {
+        let deserializer: ConfigSerializer = ConfigSerializer {};
+        deserializer.deserialize_from_str(s)
+    }
+
+    /// ### export_toml
+    ///
+    /// Serialize current configuration into a TOML string
+    pub fn export_toml(&self) -> Result<String, SerializerError> {
+        let serializer: ConfigSerializer = ConfigSerializer {};
+        serializer.serialize_to_string(&self.config)
+    }
+
+    /// ### import_toml
+    ///
+    /// Replace configuration with the one parsed from a TOML string, then write it to file.
+    /// SSH keys are not imported, since they refer to key files of another host; current SSH keys are kept
+    pub fn import_toml(&mut self, s: &str) -> Result<(), SerializerError> {
+        let mut config: UserConfig = Self::validate_toml(s)?;
+        config.remote.ssh_keys = std::mem::take(&mut self.config.remote.ssh_keys);
+        self.config = config;
+        self.write_config()
+    }
+
     /// ### write_config
     ///
     /// Write configuration to file
@@ -358,6 +661,20 @@ mod tests {
         assert_eq!(client.ssh_key_dir, ssh_keys_path);
     }
 
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_system_config_new_ssh_key_dir_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        assert!(ConfigClient::new(cfg_path.as_path(), key_path.as_path()).is_ok());
+        let mode: u32 = std::fs::metadata(key_path.as_path())
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+
     #[test]
     fn test_system_config_new_err() {
         assert!(
@@ -369,6 +686,78 @@ mod tests {
         assert!(ConfigClient::new(cfg_path.as_path(), Path::new("/tmp/efnnu/omar")).is_err());
     }
 
+    #[test]
+    fn test_system_config_recovery_fail() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        std::fs::write(cfg_path.as_path(), "[user_interface\n").unwrap();
+        assert!(ConfigClient::new_with_recovery(
+            cfg_path.as_path(),
+            key_path.as_path(),
+            RecoveryMode::Fail
+        )
+        .is_err());
+        // File is untouched
+        assert_eq!(
+            std::fs::read_to_string(cfg_path.as_path())
+                .unwrap()
+                .as_str(),
+            "[user_interface\n"
+        );
+    }
+
+    #[test]
+    fn test_system_config_recovery_backup() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        std::fs::write(cfg_path.as_path(), "[user_interface\n").unwrap();
+        let client: ConfigClient = ConfigClient::new_with_recovery(
+            cfg_path.as_path(),
+            key_path.as_path(),
+            RecoveryMode::Backup,
+        )
+        .ok()
+        .unwrap();
+        assert_eq!(client.get_default_protocol(), FileTransferProtocol::Sftp);
+        assert!(client.config_warnings()[0].contains("config.toml.corrupt"));
+        // Corrupted file has been moved aside
+        let mut backup_path: PathBuf = PathBuf::from(tmp_dir.path());
+        backup_path.push("config.toml.corrupt");
+        assert_eq!(
+            std::fs::read_to_string(backup_path.as_path())
+                .unwrap()
+                .as_str(),
+            "[user_interface\n"
+        );
+        assert!(ConfigClient::new(cfg_path.as_path(), key_path.as_path()).is_ok());
+    }
+
+    #[test]
+    fn test_system_config_validate_toml() {
+        let config: UserConfig = ConfigClient::validate_toml(
+            "[user_interface]\ndefault_protocol = \"SCP\"\ntext_editor = \"vim\"\nshow_hidden_files = true\n\n[remote.ssh_keys]\n",
+        )
+        .ok()
+        .unwrap();
+        assert_eq!(config.user_interface.default_protocol.as_str(), "SCP");
+        assert!(config.user_interface.show_hidden_local);
+        assert!(config.user_interface.show_hidden_remote);
+    }
+
+    #[test]
+    fn test_system_config_validate_toml_bad() {
+        // Missing required keys
+        assert!(
+            ConfigClient::validate_toml("[user_interface]\ndefault_protocol = \"SCP\"\n").is_err()
+        );
+        // Syntax error
+        let err: String = ConfigClient::validate_toml("[user_interface\n")
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("line 1"), "{}", err);
+    }
+
     #[test]
     fn test_system_config_from_existing() {
         let tmp_dir: tempfile::TempDir = create_tmp_dir();
@@ -413,6 +802,71 @@ mod tests {
         assert_eq!(client.get_text_editor(), PathBuf::from("mcedit"));
     }
 
+    #[test]
+    fn test_system_config_protocol_editors() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        client.set_text_editor(PathBuf::from("nano"));
+        client.config.user_interface.protocol_editors.insert(
+            FileTransferProtocol::Sftp.to_string(),
+            PathBuf::from("code"),
+        );
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(
+            client.get_editor_for(FileTransferProtocol::Sftp),
+            PathBuf::from("code")
+        );
+        // Fallback to text editor
+        assert_eq!(
+            client.get_editor_for(FileTransferProtocol::Ftp(false)),
+            PathBuf::from("nano")
+        );
+    }
+
+    #[test]
+    fn test_system_config_protocol_icons() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client
+            .get_protocol_icon(FileTransferProtocol::Sftp)
+            .is_none());
+        client
+            .config
+            .user_interface
+            .protocol_icons
+            .insert(FileTransferProtocol::Sftp.to_string(), String::from("🔒"));
+        client.config.user_interface.protocol_icons.insert(
+            FileTransferProtocol::Ftp(true).to_string(),
+            String::from("🔐"),
+        );
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(
+            client.get_protocol_icon(FileTransferProtocol::Sftp),
+            Some("🔒")
+        );
+        assert_eq!(
+            client.get_protocol_icon(FileTransferProtocol::Ftp(true)),
+            Some("🔐")
+        );
+        assert!(client
+            .get_protocol_icon(FileTransferProtocol::Ftp(false))
+            .is_none());
+    }
+
     #[test]
     fn test_system_config_default_protocol() {
         let tmp_dir: tempfile::TempDir = create_tmp_dir();
@@ -436,6 +890,223 @@ mod tests {
             .unwrap();
         client.set_show_hidden_files(true);
         assert_eq!(client.get_show_hidden_files(), true);
+        assert!(client.get_show_hidden_local());
+        assert!(client.get_show_hidden_remote());
+        // Panes are independent
+        client.config.user_interface.show_hidden_local = false;
+        assert!(!client.get_show_hidden_local());
+        assert!(client.get_show_hidden_remote());
+        assert!(!client.get_show_hidden_files());
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(!client.get_show_hidden_local());
+        assert!(client.get_show_hidden_remote());
+    }
+
+    #[test]
+    fn test_system_config_show_hidden_files_migration() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        // Write configuration with deprecated key
+        std::fs::write(
+            cfg_path.as_path(),
+            "[user_interface]\ndefault_protocol = \"SFTP\"\ntext_editor = \"vim\"\nshow_hidden_files = true\n\n[remote.ssh_keys]\n",
+        )
+        .unwrap();
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_show_hidden_local());
+        assert!(client.get_show_hidden_remote());
+        // Deprecated key is not written anymore
+        assert!(client.write_config().is_ok());
+        let data: String = std::fs::read_to_string(cfg_path.as_path()).unwrap();
+        assert!(!data.contains("show_hidden_files"));
+        assert!(data.contains("show_hidden_local = true"));
+        assert!(data.contains("show_hidden_remote = true"));
+    }
+
+    #[test]
+    fn test_system_config_confirm_remote_delete() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_confirm_remote_delete()); // Default
+        client.config.user_interface.confirm_remote_delete = false;
+        assert!(!client.get_confirm_remote_delete());
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(!client.get_confirm_remote_delete());
+    }
+
+    #[test]
+    fn test_system_config_notify_on_complete() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(!client.get_notify_on_complete()); // Default
+        client.config.user_interface.notify_on_complete = true;
+        assert!(client.get_notify_on_complete());
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_notify_on_complete());
+    }
+
+    #[test]
+    fn test_system_config_min_terminal() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_min_terminal(), (80, 24)); // Default
+        client.config.user_interface.min_terminal = (120, 40);
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_min_terminal(), (120, 40));
+    }
+
+    #[test]
+    fn test_system_config_password_prompt_timeout() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_password_prompt_timeout_secs(), None); // Default
+        client.config.user_interface.password_prompt_timeout_secs = Some(30);
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_password_prompt_timeout_secs(), Some(30));
+        // Disable timeout
+        client.config.user_interface.password_prompt_timeout_secs = None;
+        assert!(client.write_config().is_ok());
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_password_prompt_timeout_secs(), None);
+    }
+
+    #[test]
+    fn test_system_config_temp_dir() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_temp_dir().is_none()); // Default
+        let temp_dir: tempfile::TempDir = create_tmp_dir();
+        client.config.user_interface.temp_dir = Some(temp_dir.path().to_path_buf());
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_temp_dir(), Some(temp_dir.path().to_path_buf()));
+    }
+
+    #[test]
+    fn test_system_config_transfer_buffer_kb() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_transfer_buffer_kb(), 64); // Default
+        client.config.remote.transfer_buffer_kb = 0; // Clamp
+        assert_eq!(client.get_transfer_buffer_kb(), TRANSFER_BUFFER_KB_MIN);
+        client.config.remote.transfer_buffer_kb = 1048576;
+        assert_eq!(client.get_transfer_buffer_kb(), TRANSFER_BUFFER_KB_MAX);
+        client.config.remote.transfer_buffer_kb = 256;
+        assert_eq!(client.get_transfer_buffer_kb(), 256);
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_transfer_buffer_kb(), 256);
+    }
+
+    #[test]
+    fn test_system_config_transfer_buffer_kb_out_of_range() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        // Write configuration with buffer size out of range
+        std::fs::write(
+            cfg_path.as_path(),
+            "[user_interface]\ndefault_protocol = \"SFTP\"\ntext_editor = \"vim\"\n\n[remote]\ntransfer_buffer_kb = 0\n\n[remote.ssh_keys]\n",
+        )
+        .unwrap();
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_transfer_buffer_kb(), TRANSFER_BUFFER_KB_MIN);
+        std::fs::write(
+            cfg_path.as_path(),
+            "[user_interface]\ndefault_protocol = \"SFTP\"\ntext_editor = \"vim\"\n\n[remote]\ntransfer_buffer_kb = 9007199254740991\n\n[remote.ssh_keys]\n",
+        )
+        .unwrap();
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_transfer_buffer_kb(), TRANSFER_BUFFER_KB_MAX);
+    }
+
+    #[test]
+    fn test_system_config_warnings() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.config_warnings().is_empty());
+        client.set_default_protocol(FileTransferProtocol::Ftp(false));
+        let warnings: Vec<String> = client.config_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("FTP (insecure) set as default protocol"));
+        // FTPS is fine
+        client.set_default_protocol(FileTransferProtocol::Ftp(true));
+        assert!(client.config_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_system_config_enabled_protocols() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_enabled_protocols().len(), 4); // Default
+        client.config.user_interface.enabled_protocols =
+            vec![String::from("sftp"), String::from("FTPS")];
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(
+            client.get_enabled_protocols(),
+            vec![FileTransferProtocol::Sftp, FileTransferProtocol::Ftp(true)]
+        );
     }
 
     #[test]
@@ -451,6 +1122,106 @@ mod tests {
         assert_eq!(client.get_group_dirs(), None,);
     }
 
+    #[test]
+    fn test_system_config_recents_policy() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_recents_policy(), RecentsPolicy::from(16)); // Default
+        client.config.recents.max = Some(8);
+        client.config.recents.max_age = Some(3600);
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(
+            client.get_recents_policy(),
+            RecentsPolicy {
+                max: Some(8),
+                max_age: Some(Duration::from_secs(3600)),
+            }
+        );
+    }
+
+    #[test]
+    fn test_system_config_recents_store_username() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_recents_store_username()); // Default
+        client.config.recents.store_username = false;
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(!client.get_recents_store_username());
+    }
+
+    #[test]
+    fn test_system_config_session_tag() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_session_tag().is_none()); // Default
+        client.config.session_tag = Some(String::from("CHG-1234"));
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert_eq!(client.get_session_tag(), Some("CHG-1234"));
+        // Empty tag
+        client.config.session_tag = Some(String::new());
+        assert!(client.get_session_tag().is_none());
+        assert!(client.write_config().is_ok());
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_session_tag().is_none());
+    }
+
+    #[test]
+    fn test_system_config_save_passwords() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_save_passwords()); // Default
+        client.config.remote.save_passwords = false;
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(!client.get_save_passwords());
+    }
+
+    #[test]
+    fn test_system_config_always_ask_credentials() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(!client.get_always_ask_credentials()); // Default
+        client.config.remote.always_ask_credentials = true;
+        assert!(client.write_config().is_ok());
+        // Reload configuration
+        let client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        assert!(client.get_always_ask_credentials());
+    }
+
     #[test]
     fn test_system_config_ssh_keys() {
         let tmp_dir: tempfile::TempDir = create_tmp_dir();
@@ -486,6 +1257,31 @@ mod tests {
         assert!(client.del_ssh_key("192.168.1.31", "pi").is_ok());
     }
 
+    #[test]
+    fn test_system_config_verify_ssh_keys() {
+        let tmp_dir: tempfile::TempDir = create_tmp_dir();
+        let (cfg_path, key_path): (PathBuf, PathBuf) = get_paths(tmp_dir.path());
+        let mut client: ConfigClient = ConfigClient::new(cfg_path.as_path(), key_path.as_path())
+            .ok()
+            .unwrap();
+        let rsa_key: String = get_sample_rsa_key();
+        assert!(client
+            .add_ssh_key("192.168.1.31", "pi", rsa_key.as_str())
+            .is_ok());
+        assert!(client
+            .add_ssh_key("192.168.1.32", "pi", rsa_key.as_str())
+            .is_ok());
+        assert_eq!(client.verify_ssh_keys().len(), 0);
+        // Remove key file
+        let mut key_file: PathBuf = key_path.clone();
+        key_file.push("pi@192.168.1.31.key");
+        assert!(remove_file(key_file.as_path()).is_ok());
+        let errors: Vec<(String, std::io::Error)> = client.verify_ssh_keys();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, String::from("pi@192.168.1.31"));
+        assert_eq!(errors[0].1.kind(), std::io::ErrorKind::NotFound);
+    }
+
     #[test]
     fn test_system_config_make_key() {
         assert_eq!(
@@ -0,0 +1,99 @@
+//! ## CredentialStore
+//!
+//! `credential_store` is the module which provides storages for bookmarks passwords, alternative to the bookmarks file
+
+/*
+*
+*   Copyright (C) 2020-2021Christian Visintin - christian.visintin1997@gmail.com
+*
+* 	This file is part of "TermSCP"
+*
+*   TermSCP is free software: you can redistribute it and/or modify
+*   it under the terms of the GNU General Public License as published by
+*   the Free Software Foundation, either version 3 of the License, or
+*   (at your option) any later version.
+*
+*   TermSCP is distributed in the hope that it will be useful,
+*   but WITHOUT ANY WARRANTY; without even the implied warranty of
+*   MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+*   GNU General Public License for more details.
+*
+*   You should have received a copy of the GNU General Public License
+*   along with TermSCP.  If not, see <http://www.gnu.org/licenses/>.
+*
+*/
+
+// Deps
+#[cfg(feature = "with-keyring")]
+extern crate keyring;
+
+/// Value written to the bookmarks file in place of the encrypted password,
+/// when the password is kept in a `CredentialStore`
+pub const CREDENTIAL_STORE_REF: &str = "@credential-store";
+
+/// ## CredentialStore
+///
+/// CredentialStore must be implemented by all the storages which can keep bookmarks passwords.
+/// Passwords are associated to the bookmark name
+pub trait CredentialStore: Send {
+    /// ### set_password
+    ///
+    /// Store password for bookmark
+    fn set_password(&mut self, name: &str, password: &str) -> Result<(), String>;
+
+    /// ### get_password
+    ///
+    /// Get password for bookmark. Returns `None` if it doesn't exist or it can't be read
+    fn get_password(&self, name: &str) -> Option<String>;
+
+    /// ### del_password
+    ///
+    /// Delete password for bookmark
+    fn del_password(&mut self, name: &str);
+}
+
+/// ## KeyringStore
+///
+/// KeyringStore keeps passwords in the operating system keyring
+#[cfg(feature = "with-keyring")]
+pub struct KeyringStore {
+    service: String,
+}
+
+#[cfg(feature = "with-keyring")]
+impl KeyringStore {
+    /// ### new
+    ///
+    /// Instantiates a new `KeyringStore` for the provided service name
+    pub fn new(service: &str) -> Self {
+        KeyringStore {
+            service: service.to_string(),
+        }
+    }
+
+    /// ### entry
+    ///
+    /// Get keyring entry for bookmark
+    fn entry(&self, name: &str) -> Result<keyring::Entry, String> {
+        keyring::Entry::new(self.service.as_str(), name).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(feature = "with-keyring")]
+impl CredentialStore for KeyringStore {
+    fn set_password(&mut self, name: &str, password: &str) -> Result<(), String> {
+        self.entry(name)?
+            .set_password(password)
+            .map_err(|e| e.to_string())
+    }
+
+    fn get_password(&self, name: &str) -> Option<String> {
+        self.entry(name).ok()?.get_password().ok()
+    }
+
+    fn del_password(&mut self, name: &str) {
+        if let Ok(entry) = self.entry(name) {
+            let _ = entry.delete_credential();
+        }
+    }
+}
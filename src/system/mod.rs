@@ -24,7 +24,9 @@
 */
 
 // modules
+pub mod backup;
 pub mod bookmarks_client;
 pub mod config_client;
+pub mod credential_store;
 pub mod environment;
 pub mod sshkey_storage;
@@ -28,11 +28,16 @@ extern crate dirs;
 
 // Locals
 use super::{AuthActivity, Color, DialogYesNoOption, Popup};
-use crate::system::bookmarks_client::BookmarksClient;
+use crate::bookmarks::builder::BookmarkBuilder;
+use crate::bookmarks::SerializerError;
+use crate::system::bookmarks_client::{BookmarksClient, IntegrityStatus};
+#[cfg(feature = "with-keyring")]
+use crate::system::credential_store::KeyringStore;
 use crate::system::environment;
 
 // Ext
 use std::path::PathBuf;
+use std::sync::mpsc::{self, TryRecvError};
 
 impl AuthActivity {
     /// ### del_bookmark
@@ -42,7 +47,7 @@ impl AuthActivity {
         if let Some(bookmarks_cli) = self.bookmarks_client.as_mut() {
             // Iterate over kyes
             let mut name: Option<String> = None;
-            for (i, key) in bookmarks_cli.iter_bookmarks().enumerate() {
+            for (i, key) in bookmarks_cli.bookmarks_ordered().into_iter().enumerate() {
                 if i == idx {
                     name = Some(key.clone());
                     break;
@@ -60,9 +65,14 @@ impl AuthActivity {
     ///
     /// Load selected bookmark (at index) to input fields
     pub(super) fn load_bookmark(&mut self, idx: usize) {
+        // Stored passwords may have to be entered again by user
+        let fill_password: bool = match self.config_client.as_ref() {
+            Some(config_cli) => !config_cli.get_always_ask_credentials(),
+            None => true,
+        };
         if let Some(bookmarks_cli) = self.bookmarks_client.as_ref() {
             // Iterate over bookmarks
-            for (i, key) in bookmarks_cli.iter_bookmarks().enumerate() {
+            for (i, key) in bookmarks_cli.bookmarks_ordered().into_iter().enumerate() {
                 if i == idx {
                     if let Some(bookmark) = bookmarks_cli.get_bookmark(&key) {
                         // Load parameters
@@ -70,9 +80,14 @@ impl AuthActivity {
                         self.port = bookmark.1.to_string();
                         self.protocol = bookmark.2;
                         self.username = bookmark.3;
-                        if let Some(password) = bookmark.4 {
-                            self.password = password;
+                        match bookmark.4 {
+                            Some(password) if fill_password => self.password = password,
+                            Some(_) => self.password.clear(),
+                            None => {}
                         }
+                        self.post_connect_cmd = bookmarks_cli
+                            .get_bookmark_post_connect_cmd(key)
+                            .map(String::from);
                     }
                     // Break
                     break;
@@ -105,24 +120,64 @@ impl AuthActivity {
                 return;
             }
         };
+        // Passwords may be disabled by configuration
+        let save_passwords: bool = match self.config_client.as_ref() {
+            Some(config_cli) => config_cli.get_save_passwords(),
+            None => true,
+        };
         if let Some(bookmarks_cli) = self.bookmarks_client.as_mut() {
             // Check if password must be saved
-            let password: Option<String> = match self.choice_opt {
-                DialogYesNoOption::Yes => Some(self.password.clone()),
-                DialogYesNoOption::No => None,
+            let password: Option<String> = match (save_passwords, &self.choice_opt) {
+                (true, DialogYesNoOption::Yes) => Some(self.password.clone()),
+                _ => None,
             };
-            bookmarks_cli.add_bookmark(
-                name,
-                self.address.clone(),
-                port,
-                self.protocol,
-                self.username.clone(),
-                password,
-            );
+            let mut builder: BookmarkBuilder = BookmarkBuilder::new();
+            builder
+                .with_name(name.as_str())
+                .with_address(self.address.as_str())
+                .with_port(port)
+                .with_protocol(self.protocol)
+                .with_username(self.username.as_str())
+                .with_password(password.as_deref());
+            // Keep post connect command of the loaded bookmark
+            if let Some(cmd) = self.post_connect_cmd.as_ref() {
+                builder.with_post_connect_cmd(cmd.as_str());
+            }
+            if let Err(err) = bookmarks_cli.add_bookmark_built(builder.build()) {
+                self.popup = Some(Popup::Alert(
+                    Color::Red,
+                    format!("Could not save bookmark: {}", err),
+                ));
+                return;
+            }
             // Save bookmarks
             self.write_bookmarks();
         }
     }
+    /// ### toggle_bookmarks_lock
+    ///
+    /// Lock bookmarks if they're unlocked; otherwise unlock them, loading the key from the key file again
+    pub(super) fn toggle_bookmarks_lock(&mut self) {
+        let key_file: Option<PathBuf> = match environment::init_config_dir() {
+            Ok(Some(path)) => Some(environment::get_bookmarks_paths(path.as_path()).1),
+            _ => None,
+        };
+        if let Some(bookmarks_cli) = self.bookmarks_client.as_mut() {
+            if !bookmarks_cli.locked() {
+                bookmarks_cli.lock();
+                // Password loaded from a bookmark must not be kept
+                self.password.clear();
+            } else if let Some(key_file) = key_file {
+                if let Err(err) = bookmarks_cli.unlock(key_file.as_path()) {
+                    self.popup = Some(Popup::Alert(
+                        Color::Red,
+                        format!("Could not unlock bookmarks: {}", err),
+                    ));
+                }
+            }
+        }
+    }
+
     /// ### del_recent
     ///
     /// Delete recent
@@ -138,7 +193,7 @@ impl AuthActivity {
             }
             if let Some(name) = name {
                 client.del_recent(&name);
-                // Save bookmarks
+                // Save recents
                 self.write_bookmarks();
             }
         }
@@ -158,6 +213,7 @@ impl AuthActivity {
                         self.port = bookmark.1.to_string();
                         self.protocol = bookmark.2;
                         self.username = bookmark.3;
+                        self.post_connect_cmd = None;
                         // Break
                         break;
                     }
@@ -197,22 +253,46 @@ impl AuthActivity {
                 self.protocol,
                 self.username.clone(),
             );
-            // Save bookmarks
+            // Save recents
             self.write_bookmarks();
         }
     }
 
     /// ### write_bookmarks
     ///
-    /// Write bookmarks to file
+    /// Write bookmarks to file on a background thread; the result is collected by `poll_bookmarks_write`.
+    /// A previous write still in progress is waited first, so writes complete in order
     fn write_bookmarks(&mut self) {
+        self.poll_bookmarks_write(true);
         if let Some(bookmarks_cli) = self.bookmarks_client.as_ref() {
-            if let Err(err) = bookmarks_cli.write_bookmarks() {
-                self.popup = Some(Popup::Alert(
-                    Color::Red,
-                    format!("Could not write bookmarks: {}", err),
-                ));
-            }
+            let (tx, rx) = mpsc::channel();
+            bookmarks_cli.write_bookmarks_async(Box::new(move |res| {
+                let _ = tx.send(res);
+            }));
+            self.bookmarks_write = Some(rx);
+        }
+    }
+
+    /// ### poll_bookmarks_write
+    ///
+    /// Collect the result of the pending bookmarks write, if any; if `block` is true, wait for it to complete
+    pub(super) fn poll_bookmarks_write(&mut self, block: bool) {
+        let result: Option<Result<(), SerializerError>> = match self.bookmarks_write.as_ref() {
+            None => return,
+            Some(rx) if block => rx.recv().ok(),
+            Some(rx) => match rx.try_recv() {
+                Ok(result) => Some(result),
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => None,
+            },
+        };
+        self.bookmarks_write = None;
+        if let Some(Err(err)) = result {
+            self.popup = Some(Popup::Alert(
+                Color::Red,
+                format!("Could not write bookmarks: {}", err),
+            ));
+            self.redraw = true;
         }
     }
 
@@ -229,7 +309,52 @@ impl AuthActivity {
                         environment::get_bookmarks_paths(path.as_path());
                     // Initialize client
                     match BookmarksClient::new(bookmarks_file.as_path(), key_file.as_path(), 16) {
-                        Ok(cli) => self.bookmarks_client = Some(cli),
+                        Ok(mut cli) => {
+                            // Warn user if bookmarks can't be saved
+                            if cli.is_readonly() {
+                                self.popup = Some(Popup::Alert(
+                                    Color::Yellow,
+                                    format!(
+                                        "Bookmarks at \"{}\" have been saved by a newer version of termscp; changes won't be saved",
+                                        bookmarks_file.display()
+                                    ),
+                                ));
+                            }
+                            // Warn user if passwords can't be decrypted
+                            match cli.integrity_check() {
+                                IntegrityStatus::KeyMismatch => {
+                                    self.popup = Some(Popup::Alert(
+                                        Color::Yellow,
+                                        format!(
+                                            "Bookmarks passwords have been encrypted with another key than \"{}\"; saved passwords can't be used",
+                                            key_file.display()
+                                        ),
+                                    ))
+                                }
+                                IntegrityStatus::Unreadable => {
+                                    self.popup = Some(Popup::Alert(
+                                        Color::Yellow,
+                                        format!(
+                                            "Bookmarks passwords in \"{}\" are corrupted; saved passwords can't be used",
+                                            bookmarks_file.display()
+                                        ),
+                                    ))
+                                }
+                                IntegrityStatus::Ok | IntegrityStatus::NoPasswords => {}
+                            }
+                            cli.set_sanitize_names(true);
+                            // Keep passwords in the system keyring
+                            #[cfg(feature = "with-keyring")]
+                            cli.set_credential_store(Box::new(KeyringStore::new("termscp")));
+                            // Apply recents policy from configuration
+                            if let Some(config_cli) = self.config_client.as_ref() {
+                                cli.set_recents_policy(config_cli.get_recents_policy());
+                                cli.set_recents_store_username(
+                                    config_cli.get_recents_store_username(),
+                                );
+                            }
+                            self.bookmarks_client = Some(cli)
+                        }
                         Err(err) => {
                             self.popup = Some(Popup::Alert(
                                 Color::Red,
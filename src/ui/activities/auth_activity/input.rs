@@ -115,6 +115,8 @@ impl AuthActivity {
                     match self.selected_field {
                         InputField::Address => {
                             let _ = self.address.pop();
+                            // Post connect command of loaded bookmark doesn't apply to another host
+                            self.post_connect_cmd = None;
                         }
                         InputField::Password => {
                             let _ = self.password.pop();
@@ -124,6 +126,7 @@ impl AuthActivity {
                         }
                         InputField::Port => {
                             let _ = self.port.pop();
+                            self.post_connect_cmd = None;
                         }
                         _ => { /* Nothing to do */ }
                     };
@@ -161,6 +164,10 @@ impl AuthActivity {
                                 // Show setup
                                 self.setup = true;
                             }
+                            'L' | 'l' => {
+                                // Lock or unlock bookmarks
+                                self.toggle_bookmarks_lock();
+                            }
                             'S' | 's' => {
                                 // Default choice option to no
                                 self.choice_opt = DialogYesNoOption::No;
@@ -171,13 +178,22 @@ impl AuthActivity {
                         }
                     } else {
                         match self.selected_field {
-                            InputField::Address => self.address.push(ch),
+                            InputField::Address => {
+                                self.address.push(ch);
+                                // Post connect command of loaded bookmark doesn't apply to another host
+                                self.post_connect_cmd = None;
+                            }
                             InputField::Password => self.password.push(ch),
                             InputField::Username => self.username.push(ch),
                             InputField::Port => {
                                 // Value must be numeric
                                 if ch.is_numeric() {
                                     self.port.push(ch);
+                                    self.post_connect_cmd = None;
+                                    // If port is typed first, suggest protocol from it
+                                    if self.address.is_empty() {
+                                        self.guess_protocol();
+                                    }
                                 }
                             }
                             _ => { /* Nothing to do */ }
@@ -187,27 +203,23 @@ impl AuthActivity {
                 KeyCode::Left => {
                     // If current field is Protocol handle event... (move element left)
                     if self.selected_field == InputField::Protocol {
-                        self.protocol = match self.protocol {
-                            FileTransferProtocol::Sftp => FileTransferProtocol::Ftp(true), // End of list (wrap)
-                            FileTransferProtocol::Scp => FileTransferProtocol::Sftp,
-                            FileTransferProtocol::Ftp(ftps) => match ftps {
-                                false => FileTransferProtocol::Scp,
-                                true => FileTransferProtocol::Ftp(false),
-                            },
+                        let protocols: Vec<FileTransferProtocol> = self.protocol_choices();
+                        let index: usize = self.protocol_index(&protocols);
+                        self.protocol = match index {
+                            0 => protocols[protocols.len() - 1], // End of list (wrap)
+                            _ => protocols[index - 1],
                         };
+                        self.post_connect_cmd = None;
                     }
                 }
                 KeyCode::Right => {
                     // If current field is Protocol handle event... ( move element right )
                     if self.selected_field == InputField::Protocol {
-                        self.protocol = match self.protocol {
-                            FileTransferProtocol::Sftp => FileTransferProtocol::Scp,
-                            FileTransferProtocol::Scp => FileTransferProtocol::Ftp(false),
-                            FileTransferProtocol::Ftp(ftps) => match ftps {
-                                false => FileTransferProtocol::Ftp(true),
-                                true => FileTransferProtocol::Sftp, // End of list (wrap)
-                            },
-                        };
+                        let protocols: Vec<FileTransferProtocol> = self.protocol_choices();
+                        let index: usize = self.protocol_index(&protocols);
+                        // Wrap at the end of list
+                        self.protocol = protocols[(index + 1) % protocols.len()];
+                        self.post_connect_cmd = None;
                     }
                 }
                 _ => { /* Nothing to do */ }
@@ -286,6 +298,10 @@ impl AuthActivity {
                         // Show help
                         self.popup = Some(Popup::Help);
                     }
+                    'L' | 'l' => {
+                        // Lock or unlock bookmarks
+                        self.toggle_bookmarks_lock();
+                    }
                     'S' | 's' => {
                         // Default choice option to no
                         self.choice_opt = DialogYesNoOption::No;
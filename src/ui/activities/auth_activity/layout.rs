@@ -27,7 +27,9 @@
 use super::{
     AuthActivity, Context, DialogYesNoOption, FileTransferProtocol, InputField, InputForm, Popup,
 };
+use crate::system::bookmarks_client::BookmarksClient;
 use crate::utils::fmt::align_text_center;
+use crate::utils::parser::parse_color;
 // Ext
 use std::string::ToString;
 use tui::{
@@ -206,20 +208,9 @@ impl AuthActivity {
     ///
     /// Draw protocol select
     fn draw_protocol_select(&self) -> Tabs {
-        let protocols: Vec<Spans> = vec![
-            Spans::from("SFTP"),
-            Spans::from("SCP"),
-            Spans::from("FTP"),
-            Spans::from("FTPS"),
-        ];
-        let index: usize = match self.protocol {
-            FileTransferProtocol::Sftp => 0,
-            FileTransferProtocol::Scp => 1,
-            FileTransferProtocol::Ftp(ftps) => match ftps {
-                false => 2,
-                true => 3,
-            },
-        };
+        let choices: Vec<FileTransferProtocol> = self.protocol_choices();
+        let index: usize = self.protocol_index(&choices);
+        let protocols: Vec<Spans> = choices.iter().map(|x| Spans::from(x.to_string())).collect();
         Tabs::new(protocols)
             .block(
                 Block::default()
@@ -323,7 +314,8 @@ impl AuthActivity {
             .bookmarks_client
             .as_ref()
             .unwrap()
-            .iter_bookmarks()
+            .bookmarks_ordered()
+            .into_iter()
             .map(|key: &String| {
                 let entry: (String, u16, FileTransferProtocol, String, _) = self
                     .bookmarks_client
@@ -331,14 +323,31 @@ impl AuthActivity {
                     .unwrap()
                     .get_bookmark(key)
                     .unwrap();
-                ListItem::new(Span::from(format!(
-                    "{} ({}://{}@{}:{})",
-                    key,
-                    entry.2.to_string().to_lowercase(),
-                    entry.3,
-                    entry.0,
-                    entry.1
-                )))
+                let client: &BookmarksClient = self.bookmarks_client.as_ref().unwrap();
+                // Bookmark icon has priority over protocol icon
+                let icon: String = match client
+                    .get_bookmark_icon(key)
+                    .or_else(|| self.protocol_icon(entry.2))
+                {
+                    Some(icon) => format!("{} ", icon),
+                    None => String::new(),
+                };
+                let style: Style = match client.get_bookmark_color(key).and_then(parse_color) {
+                    Some(color) => Style::default().fg(color),
+                    None => Style::default(),
+                };
+                ListItem::new(Span::styled(
+                    format!(
+                        "{}{} ({}://{}@{}:{})",
+                        icon,
+                        key,
+                        entry.2.scheme(),
+                        entry.3,
+                        entry.0,
+                        entry.1
+                    ),
+                    style,
+                ))
             })
             .collect();
         // Get colors to use; highlight element inverting fg/bg only when tab is active
@@ -355,7 +364,10 @@ impl AuthActivity {
                             InputForm::Bookmarks => Style::default().fg(Color::LightGreen),
                             _ => Style::default(),
                         })
-                        .title("Bookmarks"),
+                        .title(match self.bookmarks_client.as_ref().unwrap().locked() {
+                            true => "Bookmarks (locked)",
+                            false => "Bookmarks",
+                        }),
                 )
                 .start_corner(Corner::TopLeft)
                 .highlight_style(Style::default().fg(fg).bg(bg).add_modifier(Modifier::BOLD)),
@@ -379,9 +391,14 @@ impl AuthActivity {
                     .unwrap()
                     .get_recent(key)
                     .unwrap();
+                let icon: String = match self.protocol_icon(entry.2) {
+                    Some(icon) => format!("{} ", icon),
+                    None => String::new(),
+                };
                 ListItem::new(Span::from(format!(
-                    "{}://{}@{}:{}",
-                    entry.2.to_string().to_lowercase(),
+                    "{}{}://{}@{}:{}",
+                    icon,
+                    entry.2.scheme(),
                     entry.3,
                     entry.0,
                     entry.1
@@ -409,6 +426,13 @@ impl AuthActivity {
         )
     }
 
+    /// ### protocol_icon
+    ///
+    /// Get icon to display beside connections using the provided protocol, if set in configuration
+    fn protocol_icon(&self, protocol: FileTransferProtocol) -> Option<&str> {
+        self.config_client.as_ref()?.get_protocol_icon(protocol)
+    }
+
     /// ### draw_popup_area
     ///
     /// Draw popup area
@@ -614,6 +638,16 @@ impl AuthActivity {
                 Span::raw("        "),
                 Span::raw("Show help"),
             ])),
+            ListItem::new(Spans::from(vec![
+                Span::styled(
+                    "<CTRL+L>",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("        "),
+                Span::raw("Lock/unlock bookmarks"),
+            ])),
             ListItem::new(Spans::from(vec![
                 Span::styled(
                     "<CTRL+S>",
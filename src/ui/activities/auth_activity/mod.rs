@@ -36,15 +36,17 @@ extern crate unicode_width;
 
 // locals
 use super::{Activity, Context};
+use crate::bookmarks::SerializerError;
 use crate::filetransfer::FileTransferProtocol;
 use crate::system::bookmarks_client::BookmarksClient;
-use crate::system::config_client::ConfigClient;
+use crate::system::config_client::{ConfigClient, RecoveryMode};
 use crate::system::environment;
 
 // Includes
 use crossterm::event::Event as InputEvent;
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use std::path::PathBuf;
+use std::sync::mpsc::Receiver;
 use tui::style::Color;
 
 // Types
@@ -101,11 +103,13 @@ pub struct AuthActivity {
     pub protocol: FileTransferProtocol,
     pub username: String,
     pub password: String,
-    pub submit: bool, // becomes true after user has submitted fields
-    pub quit: bool,   // Becomes true if user has pressed esc
-    pub setup: bool,  // Becomes true if user has requested setup
+    pub post_connect_cmd: Option<String>, // Command to run after connecting to the loaded bookmark
+    pub submit: bool,                     // becomes true after user has submitted fields
+    pub quit: bool,                       // Becomes true if user has pressed esc
+    pub setup: bool,                      // Becomes true if user has requested setup
     context: Option<Context>,
     bookmarks_client: Option<BookmarksClient>,
+    bookmarks_write: Option<Receiver<Result<(), SerializerError>>>, // Pending bookmarks write
     config_client: Option<ConfigClient>,
     selected_field: InputField, // Selected field in AuthCredentials Form
     popup: Option<Popup>,
@@ -135,11 +139,13 @@ impl AuthActivity {
             protocol: FileTransferProtocol::Sftp,
             username: String::new(),
             password: String::new(),
+            post_connect_cmd: None,
             submit: false,
             quit: false,
             setup: false,
             context: None,
             bookmarks_client: None,
+            bookmarks_write: None,
             config_client: None,
             selected_field: InputField::Address,
             popup: None,
@@ -153,6 +159,49 @@ impl AuthActivity {
         }
     }
 
+    /// ### protocol_choices
+    ///
+    /// Get protocols to show in the protocol select, which are the protocols enabled in configuration.
+    /// Current protocol is always part of the list, even if it's not enabled
+    fn protocol_choices(&self) -> Vec<FileTransferProtocol> {
+        let mut protocols: Vec<FileTransferProtocol> = match self.config_client.as_ref() {
+            Some(cli) => cli.get_enabled_protocols(),
+            None => vec![
+                FileTransferProtocol::Sftp,
+                FileTransferProtocol::Scp,
+                FileTransferProtocol::Ftp(false),
+                FileTransferProtocol::Ftp(true),
+            ],
+        };
+        if !protocols.contains(&self.protocol) {
+            protocols.push(self.protocol);
+        }
+        protocols
+    }
+
+    /// ### guess_protocol
+    ///
+    /// Set protocol to the one usually served on the current port, if any and if it's enabled
+    fn guess_protocol(&mut self) {
+        if let Ok(port) = self.port.parse::<u16>() {
+            if let Some(protocol) = FileTransferProtocol::guess_from_port(port) {
+                if self.protocol_choices().contains(&protocol) {
+                    self.protocol = protocol;
+                }
+            }
+        }
+    }
+
+    /// ### protocol_index
+    ///
+    /// Get index of current protocol in protocols
+    fn protocol_index(&self, protocols: &[FileTransferProtocol]) -> usize {
+        protocols
+            .iter()
+            .position(|x| *x == self.protocol)
+            .unwrap_or(0)
+    }
+
     /// ### init_config_client
     ///
     /// Initialize config client
@@ -164,10 +213,30 @@ impl AuthActivity {
                     // Get config client paths
                     let (config_path, ssh_dir): (PathBuf, PathBuf) =
                         environment::get_config_paths(config_dir.as_path());
-                    match ConfigClient::new(config_path.as_path(), ssh_dir.as_path()) {
+                    // Don't prevent startup because of a corrupted configuration
+                    match ConfigClient::new_with_recovery(
+                        config_path.as_path(),
+                        ssh_dir.as_path(),
+                        RecoveryMode::Backup,
+                    ) {
                         Ok(cli) => {
                             // Set default protocol
                             self.protocol = cli.get_default_protocol();
+                            // Warn about weak or broken configuration
+                            let mut warnings: Vec<String> = cli.config_warnings();
+                            // Warn if terminal is smaller than required
+                            let (min_cols, min_rows): (u16, u16) = cli.get_min_terminal();
+                            if let Ok((cols, rows)) = crossterm::terminal::size() {
+                                if cols < min_cols || rows < min_rows {
+                                    warnings.push(format!(
+                                        "terminal is {}x{}, but at least {}x{} is required; layout may break",
+                                        cols, rows, min_cols, min_rows
+                                    ));
+                                }
+                            }
+                            if !warnings.is_empty() {
+                                self.popup = Some(Popup::Alert(Color::Yellow, warnings.join("; ")));
+                            }
                             // Set client
                             self.config_client = Some(cli);
                         }
@@ -204,14 +273,14 @@ impl Activity for AuthActivity {
         // Put raw mode on enabled
         let _ = enable_raw_mode();
         self.popup = None;
-        // Init bookmarks client
-        if self.bookmarks_client.is_none() {
-            self.init_bookmarks_client();
-        }
         // init config client
         if self.config_client.is_none() {
             self.init_config_client();
         }
+        // Init bookmarks client (after config client, since it reads configuration)
+        if self.bookmarks_client.is_none() {
+            self.init_bookmarks_client();
+        }
     }
 
     /// ### on_draw
@@ -232,6 +301,8 @@ impl Activity for AuthActivity {
                 self.handle_input_event(&event);
             }
         }
+        // Report result of bookmarks being written
+        self.poll_bookmarks_write(false);
         // Redraw if necessary
         if self.redraw {
             // Draw
@@ -247,6 +318,8 @@ impl Activity for AuthActivity {
     /// This function must be called once before terminating the activity.
     /// This function finally releases the context
     fn on_destroy(&mut self) -> Option<Context> {
+        // Bookmarks must be written before leaving
+        self.poll_bookmarks_write(true);
         // Disable raw mode
         let _ = disable_raw_mode();
         self.context.as_ref()?;
@@ -362,7 +362,13 @@ impl FileTransferActivity {
                 if self.local.get_current_file().is_some() {
                     let file: FsEntry = self.local.get_current_file().unwrap().clone();
                     // Call upload; pass realfile, keep link name
-                    self.filetransfer_send(&file.get_realfile(), wrkdir.as_path(), Some(input));
+                    if self.filetransfer_send(
+                        &file.get_realfile(),
+                        wrkdir.as_path(),
+                        Some(input.clone()),
+                    ) {
+                        self.notify_transfer_complete(input.as_str());
+                    }
                 }
             }
             FileExplorerTab::Remote => {
@@ -371,7 +377,13 @@ impl FileTransferActivity {
                     let file: FsEntry = self.remote.get_current_file().unwrap().clone();
                     // Call upload; pass realfile, keep link name
                     let wrkdir: PathBuf = self.local.wrkdir.clone();
-                    self.filetransfer_recv(&file.get_realfile(), wrkdir.as_path(), Some(input));
+                    if self.filetransfer_recv(
+                        &file.get_realfile(),
+                        wrkdir.as_path(),
+                        Some(input.clone()),
+                    ) {
+                        self.notify_transfer_complete(input.as_str());
+                    }
                 }
             }
         }
@@ -433,7 +445,7 @@ impl FileTransferActivity {
                 // Get path on remote
                 let file_path: PathBuf = PathBuf::from(input.as_str());
                 // Create file (on local)
-                match tempfile::NamedTempFile::new() {
+                match self.new_tmpfile() {
                     Err(err) => self.log_and_alert(
                         LogLevel::Error,
                         format!("Could not create tempfile: {}", err),
@@ -298,11 +298,13 @@ impl FileTransferActivity {
                             let file: FsEntry = self.local.get_current_file().unwrap().clone();
                             let name: String = file.get_name().to_string();
                             // Call upload; pass realfile, keep link name
-                            self.filetransfer_send(
+                            if self.filetransfer_send(
                                 &file.get_realfile(),
                                 wrkdir.as_path(),
-                                Some(name),
-                            );
+                                Some(name.clone()),
+                            ) {
+                                self.notify_transfer_complete(name.as_str());
+                            }
                         }
                     }
                     _ => { /* Nothing to do */ }
@@ -380,12 +382,17 @@ impl FileTransferActivity {
                             FsEntry::Directory(dir) => dir.name.clone(),
                             FsEntry::File(file) => file.name.clone(),
                         };
-                        // Show delete prompt
-                        self.popup = Some(Popup::YesNo(
-                            format!("Delete file \"{}\"", file_name),
-                            FileTransferActivity::callback_delete_fsentry,
-                            FileTransferActivity::callback_nothing_to_do,
-                        ))
+                        // Show delete prompt, unless confirmation has been disabled
+                        match self.remote_delete_needs_confirm() {
+                            true => {
+                                self.popup = Some(Popup::YesNo(
+                                    format!("Delete file \"{}\"", file_name),
+                                    FileTransferActivity::callback_delete_fsentry,
+                                    FileTransferActivity::callback_nothing_to_do,
+                                ))
+                            }
+                            false => self.callback_delete_fsentry(),
+                        }
                     }
                 }
                 KeyCode::Char(ch) => match ch {
@@ -419,12 +426,17 @@ impl FileTransferActivity {
                                 FsEntry::Directory(dir) => dir.name.clone(),
                                 FsEntry::File(file) => file.name.clone(),
                             };
-                            // Show delete prompt
-                            self.popup = Some(Popup::YesNo(
-                                format!("Delete file \"{}\"", file_name),
-                                FileTransferActivity::callback_delete_fsentry,
-                                FileTransferActivity::callback_nothing_to_do,
-                            ))
+                            // Show delete prompt, unless confirmation has been disabled
+                            match self.remote_delete_needs_confirm() {
+                                true => {
+                                    self.popup = Some(Popup::YesNo(
+                                        format!("Delete file \"{}\"", file_name),
+                                        FileTransferActivity::callback_delete_fsentry,
+                                        FileTransferActivity::callback_nothing_to_do,
+                                    ))
+                                }
+                                false => self.callback_delete_fsentry(),
+                            }
                         }
                     }
                     'g' | 'G' => {
@@ -514,11 +526,13 @@ impl FileTransferActivity {
                             let name: String = file.get_name().to_string();
                             // Call upload; pass realfile, keep link name
                             let wrkdir: PathBuf = self.local.wrkdir.clone();
-                            self.filetransfer_recv(
+                            if self.filetransfer_recv(
                                 &file.get_realfile(),
                                 wrkdir.as_path(),
-                                Some(name),
-                            );
+                                Some(name.clone()),
+                            ) {
+                                self.notify_transfer_complete(name.as_str());
+                            }
                         }
                     }
                     _ => { /* Nothing to do */ }
@@ -20,12 +20,16 @@
 */
 
 // Locals
-use super::{Color, ConfigClient, FileTransferActivity, InputField, LogLevel, LogRecord, Popup};
+use super::{
+    Color, ConfigClient, FileExplorerTab, FileTransferActivity, InputField, LogLevel, LogRecord,
+    Popup,
+};
 use crate::fs::explorer::{builder::FileExplorerBuilder, FileExplorer, FileSorting, GroupDirs};
 use crate::system::environment;
 use crate::system::sshkey_storage::SshKeyStorage;
 // Ext
 use std::env;
+use std::io::Write;
 use std::path::PathBuf;
 
 impl FileTransferActivity {
@@ -33,8 +37,11 @@ impl FileTransferActivity {
     ///
     /// Add message to log events
     pub(super) fn log(&mut self, level: LogLevel, msg: &str) {
-        // Create log record
-        let record: LogRecord = LogRecord::new(level, msg);
+        // Create log record; prefix message with session tag, if set
+        let record: LogRecord = match self.config_cli.as_ref().and_then(|x| x.get_session_tag()) {
+            Some(tag) => LogRecord::new(level, format!("[{}] {}", tag, msg).as_str()),
+            None => LogRecord::new(level, msg),
+        };
         //Check if history overflows the size
         if self.log_records.len() + 1 > self.log_size {
             self.log_records.pop_back(); // Start cleaning events from back
@@ -125,13 +132,16 @@ impl FileTransferActivity {
 
     /// ### build_explorer
     ///
-    /// Build explorer reading configuration from `ConfigClient`
-    pub(super) fn build_explorer(cli: Option<&ConfigClient>) -> FileExplorer {
+    /// Build explorer for `tab` reading configuration from `ConfigClient`
+    pub(super) fn build_explorer(cli: Option<&ConfigClient>, tab: FileExplorerTab) -> FileExplorer {
         match &cli {
             Some(cli) => FileExplorerBuilder::new() // Build according to current configuration
                 .with_file_sorting(FileSorting::ByName)
                 .with_group_dirs(cli.get_group_dirs())
-                .with_hidden_files(cli.get_show_hidden_files())
+                .with_hidden_files(match tab {
+                    FileExplorerTab::Local => cli.get_show_hidden_local(),
+                    FileExplorerTab::Remote => cli.get_show_hidden_remote(),
+                })
                 .with_stack_size(16)
                 .build(),
             None => FileExplorerBuilder::new() // Build default
@@ -151,4 +161,65 @@ impl FileTransferActivity {
             env::set_var("EDITOR", config_cli.get_text_editor());
         }
     }
+
+    /// ### setup_remote_text_editor
+    ///
+    /// Set text editor to use for remote files, based on the protocol in use
+    pub(super) fn setup_remote_text_editor(&self) {
+        if let Some(config_cli) = &self.config_cli {
+            // Set text editor
+            env::set_var("EDITOR", config_cli.get_editor_for(self.params.protocol));
+        }
+    }
+
+    /// ### new_tmpfile
+    ///
+    /// Create a temporary file in the temporary directory set in configuration or, if not set, in system's one
+    pub(super) fn new_tmpfile(&self) -> std::io::Result<tempfile::NamedTempFile> {
+        match self.config_cli.as_ref().and_then(|x| x.get_temp_dir()) {
+            Some(dir) => tempfile::NamedTempFile::new_in(dir),
+            None => tempfile::NamedTempFile::new(),
+        }
+    }
+
+    /// ### remote_delete_needs_confirm
+    ///
+    /// Returns whether user must confirm before deleting remote files (true if configuration is not available)
+    pub(super) fn remote_delete_needs_confirm(&self) -> bool {
+        match &self.config_cli {
+            Some(config_cli) => config_cli.get_confirm_remote_delete(),
+            None => true,
+        }
+    }
+
+    /// ### notify_transfer_complete
+    ///
+    /// Send a desktop notification, if enabled in configuration, telling a transfer has completed.
+    /// The notification is sent as an OSC 9 escape sequence, which is ignored by terminals not supporting it
+    pub(super) fn notify_transfer_complete(&self, name: &str) {
+        let enabled: bool = match &self.config_cli {
+            Some(config_cli) => config_cli.get_notify_on_complete(),
+            None => false,
+        };
+        if enabled {
+            let mut stdout = std::io::stdout();
+            let _ = write!(
+                stdout,
+                "\x1b]9;termscp: transfer of \"{}\" completed\x07",
+                name
+            );
+            let _ = stdout.flush();
+        }
+    }
+
+    /// ### transfer_buffer_size
+    ///
+    /// Get size in bytes of the buffer to use to transfer files
+    pub(super) fn transfer_buffer_size(&self) -> usize {
+        let size_kb: u64 = match &self.config_cli {
+            Some(config_cli) => config_cli.get_transfer_buffer_kb(),
+            None => 64,
+        };
+        (size_kb as usize) * 1024
+    }
 }
@@ -53,6 +53,7 @@ use crossterm::event::Event as InputEvent;
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use std::collections::VecDeque;
 use std::path::PathBuf;
+use std::sync::mpsc;
 use std::time::Instant;
 use tui::style::Color;
 
@@ -69,6 +70,7 @@ pub struct FileTransferParams {
     pub protocol: FileTransferProtocol,
     pub username: Option<String>,
     pub password: Option<String>,
+    pub post_connect_cmd: Option<String>, // Local command to run once connected
 }
 
 /// ### InputField
@@ -240,6 +242,7 @@ pub struct FileTransferActivity {
     input_txt: String,                // Input text
     choice_opt: DialogYesNoOption,    // Dialog popup selected option
     transfer: TransferStates,         // Transfer states
+    post_connect_cmd: Option<mpsc::Receiver<(LogLevel, String)>>, // Outcome of the running post connect command
 }
 
 impl FileTransferActivity {
@@ -264,8 +267,8 @@ impl FileTransferActivity {
                 )),
             },
             params,
-            local: Self::build_explorer(config_client.as_ref()),
-            remote: Self::build_explorer(config_client.as_ref()),
+            local: Self::build_explorer(config_client.as_ref(), FileExplorerTab::Local),
+            remote: Self::build_explorer(config_client.as_ref(), FileExplorerTab::Remote),
             config_cli: config_client,
             tab: FileExplorerTab::Local,
             log_index: 0,
@@ -276,6 +279,7 @@ impl FileTransferActivity {
             input_txt: String::new(),
             choice_opt: DialogYesNoOption::Yes,
             transfer: TransferStates::default(),
+            post_connect_cmd: None,
         }
     }
 }
@@ -332,6 +336,8 @@ impl Activity for FileTransferActivity {
             // Redraw
             redraw = true;
         }
+        // Log outcome of post connect command, once it has terminated
+        redraw |= self.poll_post_connect_cmd();
         // Handle input events (if false, becomes true; otherwise remains true)
         redraw |= self.read_input_event();
         // @! draw interface
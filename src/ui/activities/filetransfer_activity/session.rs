@@ -40,9 +40,15 @@ use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use std::fs::OpenOptions;
 use std::io::{Read, Seek, Write};
 use std::path::{Path, PathBuf};
-use std::time::{Instant, SystemTime};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 use tui::style::Color;
 
+// Post connect commands still running after this are killed
+const POST_CONNECT_CMD_TIMEOUT: Duration = Duration::from_secs(30);
+
 impl FileTransferActivity {
     /// ### connect
     ///
@@ -67,6 +73,10 @@ impl FileTransferActivity {
                         .as_ref(),
                     );
                 }
+                // Run post connect command
+                if let Some(cmd) = self.params.post_connect_cmd.clone() {
+                    self.run_post_connect_cmd(cmd.as_str());
+                }
                 // Set state to explorer
                 self.popup = None;
                 self.reload_remote_dir();
@@ -78,6 +88,125 @@ impl FileTransferActivity {
         }
     }
 
+    /// ### run_post_connect_cmd
+    ///
+    /// Run local command after connecting; host and port are provided through
+    /// `TERMSCP_HOST` and `TERMSCP_PORT` environment variables. Output is not shown.
+    /// The command runs in background, so it doesn't block the UI; its outcome is logged once it terminates.
+    /// If it's still running after `POST_CONNECT_CMD_TIMEOUT`, it's killed
+    fn run_post_connect_cmd(&mut self, cmd: &str) {
+        #[cfg(target_family = "windows")]
+        let mut command: Command = Command::new("cmd");
+        #[cfg(target_family = "windows")]
+        command.arg("/C");
+        #[cfg(not(target_family = "windows"))]
+        let mut command: Command = Command::new("sh");
+        #[cfg(not(target_family = "windows"))]
+        command.arg("-c");
+        let child: Child = match command
+            .arg(cmd)
+            .env("TERMSCP_HOST", self.params.address.as_str())
+            .env("TERMSCP_PORT", self.params.port.to_string())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(err) => {
+                self.log(
+                    LogLevel::Error,
+                    format!("Could not run post connect command \"{}\": {}", cmd, err).as_str(),
+                );
+                return;
+            }
+        };
+        let (tx, rx) = mpsc::channel();
+        let cmd: String = cmd.to_string();
+        thread::spawn(move || {
+            let _ = tx.send(Self::wait_post_connect_cmd(child, cmd.as_str()));
+        });
+        self.post_connect_cmd = Some(rx);
+    }
+
+    /// ### wait_post_connect_cmd
+    ///
+    /// Wait for post connect command to terminate, killing it after `POST_CONNECT_CMD_TIMEOUT`.
+    /// Returns the record to log
+    fn wait_post_connect_cmd(mut child: Child, cmd: &str) -> (LogLevel, String) {
+        let started: Instant = Instant::now();
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) if status.success() => {
+                    return (
+                        LogLevel::Info,
+                        format!("Post connect command \"{}\" succeeded", cmd),
+                    )
+                }
+                Ok(Some(status)) => {
+                    let mut stderr: String = String::new();
+                    if let Some(mut pipe) = child.stderr.take() {
+                        let _ = pipe.read_to_string(&mut stderr);
+                    }
+                    return (
+                        LogLevel::Warn,
+                        format!(
+                            "Post connect command \"{}\" failed ({}): {}",
+                            cmd,
+                            status,
+                            stderr.trim()
+                        ),
+                    );
+                }
+                Ok(None) if started.elapsed() >= POST_CONNECT_CMD_TIMEOUT => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return (
+                        LogLevel::Warn,
+                        format!(
+                            "Post connect command \"{}\" killed after {} seconds",
+                            cmd,
+                            POST_CONNECT_CMD_TIMEOUT.as_secs()
+                        ),
+                    );
+                }
+                Ok(None) => thread::sleep(Duration::from_millis(100)),
+                Err(err) => {
+                    return (
+                        LogLevel::Error,
+                        format!(
+                            "Could not wait for post connect command \"{}\": {}",
+                            cmd, err
+                        ),
+                    )
+                }
+            }
+        }
+    }
+
+    /// ### poll_post_connect_cmd
+    ///
+    /// Log outcome of post connect command, if it has terminated.
+    /// Returns whether a record has been logged
+    pub(super) fn poll_post_connect_cmd(&mut self) -> bool {
+        let outcome = match self.post_connect_cmd.as_ref() {
+            Some(rx) => rx.try_recv(),
+            None => return false,
+        };
+        match outcome {
+            Ok((level, msg)) => {
+                self.post_connect_cmd = None;
+                self.log(level, msg.as_str());
+                true
+            }
+            Err(mpsc::TryRecvError::Empty) => false,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.post_connect_cmd = None;
+                false
+            }
+        }
+    }
+
     /// ### disconnect
     ///
     /// disconnect from remote
@@ -117,13 +246,14 @@ impl FileTransferActivity {
     ///
     /// Send fs entry to remote.
     /// If dst_name is Some, entry will be saved with a different name.
-    /// If entry is a directory, this applies to directory only
+    /// If entry is a directory, this applies to directory only.
+    /// Returns whether the entry has been sent; false if transfer failed or has been aborted
     pub(super) fn filetransfer_send(
         &mut self,
         entry: &FsEntry,
         curr_remote_path: &Path,
         dst_name: Option<String>,
-    ) {
+    ) -> bool {
         // Write popup
         let file_name: String = match entry {
             FsEntry::Directory(dir) => dir.name.clone(),
@@ -139,10 +269,13 @@ impl FileTransferActivity {
             None => PathBuf::from(file_name.as_str()),
         };
         remote_path.push(remote_file_name);
+        let mut sent: bool = true;
         // Match entry
         match entry {
             FsEntry::File(file) => {
-                let _ = self.filetransfer_send_file(file, remote_path.as_path());
+                sent = self
+                    .filetransfer_send_file(file, remote_path.as_path())
+                    .is_ok();
             }
             FsEntry::Directory(dir) => {
                 // Create directory on remote
@@ -168,10 +301,14 @@ impl FileTransferActivity {
                                         break;
                                     }
                                     // Send entry; name is always None after first call
-                                    self.filetransfer_send(&entry, remote_path.as_path(), None);
+                                    if !self.filetransfer_send(&entry, remote_path.as_path(), None)
+                                    {
+                                        sent = false;
+                                    }
                                 }
                             }
                             Err(err) => {
+                                sent = false;
                                 self.log_and_alert(
                                     LogLevel::Error,
                                     format!(
@@ -184,6 +321,7 @@ impl FileTransferActivity {
                         }
                     }
                     Err(err) => {
+                        sent = false;
                         self.log_and_alert(
                             LogLevel::Error,
                             format!(
@@ -208,6 +346,7 @@ impl FileTransferActivity {
             );
             // Set aborted to false
             self.transfer.aborted = false;
+            false
         } else {
             // @! Successful
             // Eventually, Reset input mode to explorer (if input mode is wait or progress)
@@ -216,6 +355,7 @@ impl FileTransferActivity {
                     self.popup = None
                 }
             }
+            sent
         }
     }
 
@@ -223,13 +363,14 @@ impl FileTransferActivity {
     ///
     /// Recv fs entry from remote.
     /// If dst_name is Some, entry will be saved with a different name.
-    /// If entry is a directory, this applies to directory only
+    /// If entry is a directory, this applies to directory only.
+    /// Returns whether the entry has been received; false if transfer failed or has been aborted
     pub(super) fn filetransfer_recv(
         &mut self,
         entry: &FsEntry,
         local_path: &Path,
         dst_name: Option<String>,
-    ) {
+    ) -> bool {
         // Write popup
         let file_name: String = match entry {
             FsEntry::Directory(dir) => dir.name.clone(),
@@ -238,6 +379,7 @@ impl FileTransferActivity {
         self.popup = Some(Popup::Wait(format!("Downloading \"{}\"...", file_name)));
         // Draw
         self.draw();
+        let mut received: bool = true;
         // Match entry
         match entry {
             FsEntry::File(file) => {
@@ -250,6 +392,7 @@ impl FileTransferActivity {
                 local_file_path.push(local_file_name.as_str());
                 // Download file
                 if let Err(err) = self.filetransfer_recv_file(local_file_path.as_path(), file) {
+                    received = false;
                     self.log_and_alert(LogLevel::Error, err);
                 }
             }
@@ -306,10 +449,17 @@ impl FileTransferActivity {
                                     }
                                     // Receive entry; name is always None after first call
                                     // Local path becomes local_dir_path
-                                    self.filetransfer_recv(&entry, local_dir_path.as_path(), None);
+                                    if !self.filetransfer_recv(
+                                        &entry,
+                                        local_dir_path.as_path(),
+                                        None,
+                                    ) {
+                                        received = false;
+                                    }
                                 }
                             }
                             Err(err) => {
+                                received = false;
                                 self.log_and_alert(
                                     LogLevel::Error,
                                     format!(
@@ -322,6 +472,7 @@ impl FileTransferActivity {
                         }
                     }
                     Err(err) => {
+                        received = false;
                         self.log(
                             LogLevel::Error,
                             format!(
@@ -349,9 +500,11 @@ impl FileTransferActivity {
             );
             // Reset aborted to false
             self.transfer.aborted = false;
+            false
         } else {
             // Eventually, Reset input mode to explorer
             self.popup = None;
+            received
         }
     }
 
@@ -385,6 +538,7 @@ impl FileTransferActivity {
                     self.transfer.reset();
                     let mut last_progress_val: f64 = 0.0;
                     let mut last_input_event_fetch: Instant = Instant::now();
+                    let mut buffer: Vec<u8> = vec![0; self.transfer_buffer_size()];
                     // While the entire file hasn't been completely written,
                     // Or filetransfer has been aborted
                     while total_bytes_written < file_size && !self.transfer.aborted {
@@ -396,7 +550,6 @@ impl FileTransferActivity {
                             last_input_event_fetch = Instant::now();
                         }
                         // Read till you can
-                        let mut buffer: [u8; 65536] = [0; 65536];
                         match fhnd.read(&mut buffer) {
                             Ok(bytes_read) => {
                                 total_bytes_written += bytes_read;
@@ -490,6 +643,7 @@ impl FileTransferActivity {
                         // Write local file
                         let mut last_progress_val: f64 = 0.0;
                         let mut last_input_event_fetch: Instant = Instant::now();
+                        let mut buffer: Vec<u8> = vec![0; self.transfer_buffer_size()];
                         // While the entire file hasn't been completely read,
                         // Or filetransfer has been aborted
                         while total_bytes_written < remote.size && !self.transfer.aborted {
@@ -501,7 +655,6 @@ impl FileTransferActivity {
                                 last_input_event_fetch = Instant::now();
                             }
                             // Read till you can
-                            let mut buffer: [u8; 65536] = [0; 65536];
                             match rhnd.read(&mut buffer) {
                                 Ok(bytes_read) => {
                                     total_bytes_written += bytes_read;
@@ -768,7 +921,7 @@ impl FileTransferActivity {
     /// Edit file on remote host
     pub(super) fn edit_remote_file(&mut self, file: &FsFile) -> Result<(), String> {
         // Create temp file
-        let tmpfile: tempfile::NamedTempFile = match tempfile::NamedTempFile::new() {
+        let tmpfile: tempfile::NamedTempFile = match self.new_tmpfile() {
             Ok(f) => f,
             Err(err) => {
                 return Err(format!("Could not create temporary file: {}", err));
@@ -790,8 +943,11 @@ impl FileTransferActivity {
                 ))
             }
         };
-        // Edit file
-        if let Err(err) = self.edit_local_file(tmpfile.path()) {
+        // Edit file using the text editor for the remote protocol; then restore text editor
+        self.setup_remote_text_editor();
+        let result: Result<(), String> = self.edit_local_file(tmpfile.path());
+        self.setup_text_editor();
+        if let Err(err) = result {
             return Err(err);
         }
         // Get local fs entry
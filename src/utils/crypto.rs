@@ -24,14 +24,51 @@
 */
 
 // Deps
+extern crate aes_gcm;
+extern crate base64;
 extern crate magic_crypt;
+extern crate rand;
+extern crate sha2;
 
 // Ext
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
 use magic_crypt::MagicCryptTrait;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// Prefix of secrets encrypted with AES-256-GCM
+pub const AEAD_PREFIX: &str = "gcm:";
+const AEAD_KEY_CHECK_LEN: usize = 4;
+const AEAD_NONCE_LEN: usize = 12;
+
+/// ## AeadError
+///
+/// Describes the error returned when decrypting an authenticated secret
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AeadError {
+    BadEncoding, // Secret is not a valid AEAD secret
+    KeyMismatch, // Secret has been encrypted with another key
+    Integrity,   // Secret has been tampered
+}
+
+impl std::fmt::Display for AeadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let err: &str = match self {
+            AeadError::BadEncoding => "secret is not encoded properly",
+            AeadError::KeyMismatch => "secret has been encrypted with a different key",
+            AeadError::Integrity => "secret has been tampered",
+        };
+        write!(f, "{}", err)
+    }
+}
 
 /// ### aes128_b64_crypt
 ///
-/// Crypt a string using AES128; output is returned as a BASE64 string
+/// Crypt a string using AES128; output is returned as a BASE64 string.
+/// Not used anymore to store secrets, which are now encrypted with `aes256_gcm_b64_crypt`;
+/// kept to make legacy secrets in tests
+#[cfg(test)]
 pub fn aes128_b64_crypt(key: &str, input: &str) -> String {
     let crypter = new_magic_crypt!(key.to_string(), 128);
     crypter.encrypt_str_to_base64(input.to_string())
@@ -45,6 +82,92 @@ pub fn aes128_b64_decrypt(key: &str, secret: &str) -> Result<String, magic_crypt
     crypter.decrypt_base64_to_string(secret.to_string())
 }
 
+/// ### aes256_gcm_b64_crypt
+///
+/// Crypt a string using AES256-GCM; output is returned as a BASE64 string, prefixed with `AEAD_PREFIX`.
+/// The encoded payload is made up of a key check value, the nonce and the ciphertext with its tag
+pub fn aes256_gcm_b64_crypt(key: &str, input: &str) -> String {
+    let (cipher_key, key_check) = aead_key(key);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&cipher_key));
+    let mut nonce: [u8; AEAD_NONCE_LEN] = [0; AEAD_NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext: Vec<u8> = cipher
+        .encrypt(Nonce::from_slice(&nonce), input.as_bytes())
+        .expect("AES-GCM encryption failed");
+    let mut payload: Vec<u8> = Vec::with_capacity(key_check.len() + nonce.len() + ciphertext.len());
+    payload.extend_from_slice(&key_check);
+    payload.extend_from_slice(&nonce);
+    payload.extend_from_slice(&ciphertext);
+    format!("{}{}", AEAD_PREFIX, base64::encode(payload))
+}
+
+/// ### aes256_gcm_b64_decrypt
+///
+/// Decrypt a string encrypted with `aes256_gcm_b64_crypt`.
+/// Returns `AeadError::Integrity` if the secret has been tampered
+pub fn aes256_gcm_b64_decrypt(key: &str, secret: &str) -> Result<String, AeadError> {
+    let payload: Vec<u8> = match secret.strip_prefix(AEAD_PREFIX) {
+        Some(encoded) => base64::decode(encoded).map_err(|_| AeadError::BadEncoding)?,
+        None => return Err(AeadError::BadEncoding),
+    };
+    if payload.len() < AEAD_KEY_CHECK_LEN + AEAD_NONCE_LEN {
+        return Err(AeadError::BadEncoding);
+    }
+    let (cipher_key, key_check) = aead_key(key);
+    if payload[..AEAD_KEY_CHECK_LEN] != key_check {
+        return Err(AeadError::KeyMismatch);
+    }
+    let (nonce, ciphertext) = payload[AEAD_KEY_CHECK_LEN..].split_at(AEAD_NONCE_LEN);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&cipher_key));
+    let plain: Vec<u8> = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| AeadError::Integrity)?;
+    String::from_utf8(plain).map_err(|_| AeadError::BadEncoding)
+}
+
+/// ### aes256_gcm_seal
+///
+/// Encrypt `input` using AES256-GCM with a 256 bit key; returns the nonce followed by the ciphertext with its tag.
+/// No key check value is included, so a wrong key is only detected by the authentication tag
+pub fn aes256_gcm_seal(key: &[u8; 32], input: &[u8]) -> Vec<u8> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let mut nonce: [u8; AEAD_NONCE_LEN] = [0; AEAD_NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext: Vec<u8> = cipher
+        .encrypt(Nonce::from_slice(&nonce), input)
+        .expect("AES-GCM encryption failed");
+    let mut payload: Vec<u8> = Vec::with_capacity(nonce.len() + ciphertext.len());
+    payload.extend_from_slice(&nonce);
+    payload.extend_from_slice(&ciphertext);
+    payload
+}
+
+/// ### aes256_gcm_open
+///
+/// Decrypt a payload encrypted with `aes256_gcm_seal`.
+/// Returns `AeadError::Integrity` if the key is wrong or the payload has been tampered
+pub fn aes256_gcm_open(key: &[u8; 32], payload: &[u8]) -> Result<Vec<u8>, AeadError> {
+    if payload.len() < AEAD_NONCE_LEN {
+        return Err(AeadError::BadEncoding);
+    }
+    let (nonce, ciphertext) = payload.split_at(AEAD_NONCE_LEN);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| AeadError::Integrity)
+}
+
+/// ### aead_key
+///
+/// Derive the AES256 key from the provided key and a short check value used to detect key mismatches
+fn aead_key(key: &str) -> ([u8; 32], [u8; AEAD_KEY_CHECK_LEN]) {
+    let mut cipher_key: [u8; 32] = [0; 32];
+    cipher_key.copy_from_slice(&Sha256::digest(key.as_bytes()));
+    let mut key_check: [u8; AEAD_KEY_CHECK_LEN] = [0; AEAD_KEY_CHECK_LEN];
+    key_check.copy_from_slice(&Sha256::digest(cipher_key)[..AEAD_KEY_CHECK_LEN]);
+    (cipher_key, key_check)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -64,4 +187,66 @@ mod tests {
             input
         );
     }
+
+    #[test]
+    fn test_utils_crypto_aes256_gcm() {
+        let key: &str = "MYSUPERSECRETKEY";
+        let input: &str = "Hello world!";
+        let secret: String = aes256_gcm_b64_crypt(key, input);
+        assert!(secret.starts_with(AEAD_PREFIX));
+        // Nonce is random
+        assert_ne!(secret, aes256_gcm_b64_crypt(key, input));
+        assert_eq!(
+            aes256_gcm_b64_decrypt(key, secret.as_str())
+                .unwrap()
+                .as_str(),
+            input
+        );
+        // Bad key
+        assert_eq!(
+            aes256_gcm_b64_decrypt("ANOTHERKEY", secret.as_str())
+                .err()
+                .unwrap(),
+            AeadError::KeyMismatch
+        );
+        // Tampered
+        let mut payload: Vec<u8> = base64::decode(&secret[AEAD_PREFIX.len()..]).unwrap();
+        let last: usize = payload.len() - 1;
+        payload[last] ^= 0x01;
+        let tampered: String = format!("{}{}", AEAD_PREFIX, base64::encode(payload));
+        assert_eq!(
+            aes256_gcm_b64_decrypt(key, tampered.as_str())
+                .err()
+                .unwrap(),
+            AeadError::Integrity
+        );
+        // Bad encoding
+        assert_eq!(
+            aes256_gcm_b64_decrypt(key, "z4Z6LpcpYqBW4+bkIok+5A==")
+                .err()
+                .unwrap(),
+            AeadError::BadEncoding
+        );
+    }
+
+    #[test]
+    fn test_utils_crypto_aes256_gcm_seal() {
+        let key: [u8; 32] = [7; 32];
+        let payload: Vec<u8> = aes256_gcm_seal(&key, b"Hello world!");
+        assert_eq!(
+            aes256_gcm_open(&key, payload.as_slice())
+                .unwrap()
+                .as_slice(),
+            b"Hello world!"
+        );
+        // Bad key is detected by tag
+        assert_eq!(
+            aes256_gcm_open(&[8; 32], payload.as_slice()).err().unwrap(),
+            AeadError::Integrity
+        );
+        assert_eq!(
+            aes256_gcm_open(&key, &payload[..4]).err().unwrap(),
+            AeadError::BadEncoding
+        );
+    }
 }
@@ -37,6 +37,7 @@ use chrono::format::ParseError;
 use chrono::prelude::*;
 use std::str::FromStr;
 use std::time::{Duration, SystemTime};
+use tui::style::Color;
 
 /// ### parse_remote_opt
 ///
@@ -196,6 +197,39 @@ pub fn parse_datetime(tm: &str, fmt: &str) -> Result<SystemTime, ParseError> {
     }
 }
 
+/// ### parse_color
+///
+/// Parse a color, which can be either a named color (e.g. `red`, `lightblue`) or an hex color (`#rrggbb`).
+/// Returns `None` if the color is not valid
+pub fn parse_color(color: &str) -> Option<Color> {
+    match color.to_lowercase().as_str() {
+        "black" => Some(Color::Black),
+        "red" => Some(Color::Red),
+        "green" => Some(Color::Green),
+        "yellow" => Some(Color::Yellow),
+        "blue" => Some(Color::Blue),
+        "magenta" => Some(Color::Magenta),
+        "cyan" => Some(Color::Cyan),
+        "gray" => Some(Color::Gray),
+        "darkgray" => Some(Color::DarkGray),
+        "lightred" => Some(Color::LightRed),
+        "lightgreen" => Some(Color::LightGreen),
+        "lightyellow" => Some(Color::LightYellow),
+        "lightblue" => Some(Color::LightBlue),
+        "lightmagenta" => Some(Color::LightMagenta),
+        "lightcyan" => Some(Color::LightCyan),
+        "white" => Some(Color::White),
+        hex => {
+            let hex: &str = hex.strip_prefix('#')?;
+            if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+            Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -338,6 +372,18 @@ mod tests {
         assert!(parse_lstime("Feb 15 25:32", "%b %d %Y", "%b %d %H:%M").is_err());
     }
 
+    #[test]
+    fn test_utils_parse_color() {
+        assert_eq!(parse_color("red"), Some(Color::Red));
+        assert_eq!(parse_color("LightBlue"), Some(Color::LightBlue));
+        assert_eq!(parse_color("#ff8000"), Some(Color::Rgb(255, 128, 0)));
+        assert_eq!(parse_color("#FF8000"), Some(Color::Rgb(255, 128, 0)));
+        assert!(parse_color("pippo").is_none());
+        assert!(parse_color("#ff80").is_none());
+        assert!(parse_color("#gg8000").is_none());
+        assert!(parse_color("").is_none());
+    }
+
     #[test]
     fn test_utils_parse_datetime() {
         assert_eq!(